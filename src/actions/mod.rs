@@ -3,12 +3,25 @@ pub mod command;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use structs::app::events;
-use structs::app::events::Key;
+use structs::app::events::{Key, Mouse};
+use structs::app::{Alert, Completion, KeySettingItem, Palette, Quote, RiskLimits, Ticket};
+use structs::ui::{CursorMove, Pane, Scroll};
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AppAction {
     Keyboard(Key),
-    CommandInvalid(String),
+    /// Keys typed so far of a sequence, empty once it ran or was dropped.
+    KeyPending(Vec<Key>),
+    /// A sequence waited long enough, see `KeyboardMiddleWare`.
+    KeyTimeout(usize),
+    /// Clicks and wheel turns, see `MouseMiddleWare`.
+    Mouse(Mouse),
+    /// The terminal was resized to this many columns and rows.
+    Resize(u16, u16),
+    CommandInvalid {
+        uuid: String,
+        reason: String,
+    },
     CommandCreate(String),
     //    CommandRun {
     //        func: CommandGen,
@@ -20,12 +33,98 @@ pub enum AppAction {
         reason: String,
     },
     CommandConsume(String),
+    CommandEnqueue {
+        uuid: String,
+        cmd_str: String,
+    },
+    CommandOutput {
+        uuid: String,
+        line: String,
+        stderr: bool,
+    },
+    CommandOutputClear(String),
+    CommandFocus(Option<String>),
+    StdinSend {
+        uuid: String,
+        line: String,
+    },
+    StdinClose(String),
     CommandBarPush(char),
     CommandBarPop(u16),
+    CommandBarDelete(u16),
+    CommandBarMove(CursorMove),
+    /// Cuts up to where the cursor would move into the kill buffer.
+    CommandBarKill(CursorMove),
+    /// Pastes the kill buffer at the cursor.
+    CommandBarYank,
+    /// Puts text in the kill buffer and the terminal's clipboard.
+    Yank(String),
     CommandBarSet(String),
     CommandBarEnqueueCmd(String),
+    /// Completes the word before the cursor, see `CompletionSet`.
+    CommandBarComplete,
+    CompletionSet(Option<Completion>),
+    /// Inserts the next (or with a negative step, previous) candidate.
+    CompletionSelect(isize),
+    /// Collects commands and keys, see `PaletteSet`.
+    PaletteOpen,
+    PaletteSet(Option<Palette>),
+    PaletteQuery(String),
+    PaletteSelect(isize),
+    /// Puts the selected command in the command bar and closes the palette.
+    PaletteAccept,
+    /// Bindings looked up by the keyboard middleware, first match wins.
+    KeymapSet(Vec<KeySettingItem>),
+    /// Lines kept by each output.
+    ScrollbackSet(usize),
+    HistoryLoad(Vec<String>),
+    HistoryPrev,
+    HistoryNext,
+    /// Starts or refines a reverse search with the given query.
+    HistorySearch(String),
+    /// Jumps to the next older match of the current search.
+    HistorySearchNext,
+    /// Ends the search, keeping the match in the command bar or not.
+    HistorySearchEnd(bool),
     ConsolePush(String),
+    ConsoleHighlight {
+        line: String,
+        color: String,
+    },
     SetMode(Value),
+    TabOpen(String),
+    TabClose(usize),
+    TabNext,
+    TabPrevious,
+    TabSelect(usize),
+    /// Scrolls the output, or moves the selection of the markets pane.
+    Scroll(Scroll),
+    PaneFocus(Pane),
+    PaneFocusNext,
+    /// Focuses the markets pane on a row.
+    MarketSelect(usize),
+    /// Moves the end of the visual selection.
+    SelectionMove(Scroll),
+    /// Yanks the visual selection and leaves visual mode.
+    SelectionYank,
+    TicketSet(Option<Ticket>),
+    /// Moves to the next (or with a negative step, previous) field.
+    TicketSelect(isize),
+    TicketInput(char),
+    TicketErase,
+    /// Switches between buy and sell.
+    TicketSide,
+    /// Places the order, or tells what's wrong with the ticket.
+    TicketSubmit,
+    /// Limits orders are checked against.
+    RiskSet(RiskLimits),
+    /// Markets subscribed to, known before their first quote.
+    MarketsSet(Vec<String>),
+    QuoteUpdate(Quote),
+    AlertCreate(Alert),
+    AlertTrigger(usize),
+    AlertRemove(usize),
+    AlertFlashClear(usize),
 }
 
 impl AppAction {
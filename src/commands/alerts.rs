@@ -0,0 +1,130 @@
+use actions::AppAction;
+use commands::{self, Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use structs::app::Alert;
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(AlertCommand {}));
+    registry.register(Box::new(AlertsCommand {}));
+    registry.register(Box::new(UnalertCommand {}));
+}
+
+pub struct AlertCommand {}
+
+impl CommandSpec for AlertCommand {
+    fn name(&self) -> &'static str {
+        "alert"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        // [spread] SYMBOL OP VALUE [exec CMD], parsed by `Alert::parse`
+        const ARGS: &'static [Arg] = &[Arg::repeated("CONDITION", ArgKind::Text)];
+        ARGS
+    }
+
+    fn usage(&self) -> String {
+        String::from("alert [spread] SYMBOL OP VALUE [exec CMD]")
+    }
+
+    fn help(&self) -> &'static str {
+        "Alert once a price or spread crosses a threshold"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "alert BTC_ETH > 0.032",
+            "alert spread BTC_ETH > 0.5% exec notify-send spread",
+        ]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let id = env.state.alerts.iter().map(|a| a.id).max().unwrap_or(0) + 1;
+        let alert = Alert::parse(id, call.args)?;
+        Ok(vec![
+            AppAction::ConsolePush(format_output!(
+                "green",
+                format!("ALERT {}", id),
+                alert.describe()
+            )),
+            AppAction::AlertCreate(alert),
+        ])
+    }
+
+    fn complete(&self, env: &CommandEnv, args: &[&str], word: &str) -> Vec<String> {
+        if let Some(index) = args.iter().position(|arg| *arg == "exec") {
+            // completed as the arguments of `:exec`
+            return commands::complete(env, &commands::subline(&args[index..], word));
+        }
+        let spread = args.first() == Some(&"spread");
+        match args.len() {
+            0 => {
+                let mut candidates = commands::candidates(env, ArgKind::Symbol, word);
+                candidates.push(String::from("spread"));
+                candidates
+            }
+            1 if spread => commands::candidates(env, ArgKind::Symbol, word),
+            // after the value
+            n if n == 4 || (n == 5 && spread) => vec![String::from("exec")],
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub struct AlertsCommand {}
+
+impl CommandSpec for AlertsCommand {
+    fn name(&self) -> &'static str {
+        "alerts"
+    }
+
+    fn help(&self) -> &'static str {
+        "List alerts"
+    }
+
+    fn execute(&self, env: &CommandEnv, _call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let mut lines = vec![AppAction::ConsolePush(format_output!(
+            "white",
+            "Alerts",
+            format!("{} set", env.state.alerts.len())
+        ))];
+        for alert in env.state.alerts.iter() {
+            let status = match alert.triggered {
+                true => "triggered",
+                false => "armed",
+            };
+            let line = format!("{} [{}]", alert.describe(), status);
+            lines.push(AppAction::ConsolePush(format_output!(
+                "white", alert.id, line
+            )));
+        }
+        Ok(lines)
+    }
+}
+
+pub struct UnalertCommand {}
+
+impl CommandSpec for UnalertCommand {
+    fn name(&self) -> &'static str {
+        "unalert"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::required("ALERT", ArgKind::AlertId)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Remove an alert"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let id = call
+            .arg(0)
+            .and_then(|id| id.parse::<usize>().ok())
+            .filter(|id| env.state.alerts.iter().any(|alert| alert.id == *id))
+            .ok_or_else(|| String::from("No such alert"))?;
+        Ok(vec![
+            AppAction::ConsolePush(format_output!("green", format!("ALERT {}", id), "Removed")),
+            AppAction::AlertRemove(id),
+        ])
+    }
+}
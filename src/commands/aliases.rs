@@ -0,0 +1,135 @@
+use actions::AppAction;
+use commands::{Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use structs::app::Aliases;
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(AliasCommand { name: "alias" }));
+    registry.register(Box::new(AliasCommand { name: "macro" }));
+    registry.register(Box::new(UnaliasCommand {}));
+}
+
+/// `:alias` and `:macro`, which only differ in how the body is stored.
+pub struct AliasCommand {
+    name: &'static str,
+}
+
+impl CommandSpec for AliasCommand {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[
+            Arg::optional("NAME", ArgKind::Text),
+            Arg::repeated("= COMMAND", ArgKind::Text),
+        ];
+        ARGS
+    }
+
+    fn usage(&self) -> String {
+        format!("{} [NAME [= COMMAND]]", self.name)
+    }
+
+    // anything goes, the definition is parsed from the raw line
+    fn validate(&self, _call: &CommandCall) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn help(&self) -> &'static str {
+        match self.name {
+            "alias" => "Define a shorthand, extra arguments are appended to it",
+            _ => "Define a sequence of commands separated by ;, using $1.. and $@",
+        }
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        match self.name {
+            "alias" => &["alias ll = exec ls -l", "alias"],
+            _ => &["macro watch = alert $1 > $2 ; every 1m exec ./log.sh $1"],
+        }
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        // definitions keep their `$1`s and quotes, so they're taken from
+        // the raw line rather than the expanded tokens
+        let args = call
+            .line
+            .trim_start()
+            .get(self.name.len()..)
+            .unwrap_or("")
+            .trim();
+        let mut aliases = env.aliases.lock().expect("Aliases Lock Poisoned");
+        if !args.contains('=') {
+            return Ok(list_aliases(&aliases, args));
+        }
+        let (name, body) = Aliases::parse_definition(args)?;
+        match self.name {
+            "alias" => aliases.define_alias(name.clone(), body)?,
+            _ => aliases.define_macro(name.clone(), body)?,
+        }
+        Ok(vec![AppAction::ConsolePush(format_output!(
+            "green",
+            self.name.to_uppercase(),
+            format!("{} defined", name)
+        ))])
+    }
+}
+
+// Every definition, or only the one called `name`.
+fn list_aliases(aliases: &Aliases, name: &str) -> Vec<AppAction> {
+    let aliases_iter = aliases
+        .aliases
+        .iter()
+        .map(|(alias, body)| (alias, format!("= {}", body)));
+    let macros_iter = aliases
+        .macros
+        .iter()
+        .map(|(alias, steps)| (alias, format!("= {}", steps.join(" ; "))));
+    let mut lines = vec![AppAction::ConsolePush(format_output!(
+        "white",
+        "Aliases",
+        format!("{} defined", aliases.aliases.len() + aliases.macros.len())
+    ))];
+    lines.extend(
+        aliases_iter
+            .chain(macros_iter)
+            .filter(|(alias, _)| name.is_empty() || alias.as_str() == name)
+            .map(|(alias, body)| AppAction::ConsolePush(format_output!("white", alias, body))),
+    );
+    lines
+}
+
+pub struct UnaliasCommand {}
+
+impl CommandSpec for UnaliasCommand {
+    fn name(&self) -> &'static str {
+        "unalias"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::required("NAME", ArgKind::Text)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Remove an alias or macro"
+    }
+
+    fn complete(&self, env: &CommandEnv, args: &[&str], _word: &str) -> Vec<String> {
+        match args.len() {
+            0 => env.aliases.lock().expect("Aliases Lock Poisoned").names(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let name = call.arg(0).unwrap_or("");
+        env.aliases
+            .lock()
+            .expect("Aliases Lock Poisoned")
+            .remove(name)?;
+        Ok(vec![AppAction::ConsolePush(format_output!(
+            "green", name, "Removed"
+        ))])
+    }
+}
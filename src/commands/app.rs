@@ -0,0 +1,24 @@
+use actions::AppAction;
+use commands::{CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use structs::app::events;
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(QuitCommand {}));
+}
+
+pub struct QuitCommand {}
+
+impl CommandSpec for QuitCommand {
+    fn name(&self) -> &'static str {
+        "q"
+    }
+
+    fn help(&self) -> &'static str {
+        "Quit"
+    }
+
+    fn execute(&self, env: &CommandEnv, _call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        env.tx.send(events::Event::Exit).expect("Failed to Send");
+        Ok(vec![AppAction::ConsolePush("Exiting...".to_string())])
+    }
+}
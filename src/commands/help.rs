@@ -0,0 +1,78 @@
+use actions::AppAction;
+use commands::{Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use middlewares::keyboard::key_help;
+
+/// Output buffer, and tab, the help is written to.
+pub const HELP_BUFFER: &'static str = "help";
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(HelpCommand {}));
+}
+
+pub struct HelpCommand {}
+
+impl CommandSpec for HelpCommand {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::optional("COMMAND", ArgKind::CommandName)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "List commands and keys, or show how to use one command"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["help", "help exec"]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let lines = match call.arg(0) {
+            Some(name) => {
+                let command = env
+                    .registry
+                    .get(name)
+                    .ok_or_else(|| format!("Unknown command {:?}", name))?;
+                let mut lines = vec![
+                    format!(":{}", command.usage()),
+                    String::new(),
+                    format!("    {}", command.help()),
+                ];
+                if !command.examples().is_empty() {
+                    lines.push(String::new());
+                    lines.push(String::from("Examples"));
+                    for example in command.examples() {
+                        lines.push(format!("    :{}", example));
+                    }
+                }
+                lines
+            }
+            None => {
+                let mut lines = vec![String::from("Commands"), String::new()];
+                for command in env.registry.iter() {
+                    lines.push(format!("    :{:<40} {}", command.usage(), command.help()));
+                }
+                lines.push(String::new());
+                lines.push(String::from("Keys"));
+                lines.push(String::new());
+                for (mode, keys, description) in key_help(&env.state.keymap) {
+                    lines.push(format!("    {:<16} {:<28} {}", mode, keys, description));
+                }
+                lines
+            }
+        };
+        let mut actions = vec![AppAction::CommandOutputClear(HELP_BUFFER.to_string())];
+        for line in lines {
+            actions.push(AppAction::CommandOutput {
+                uuid: HELP_BUFFER.to_string(),
+                line,
+                stderr: false,
+            });
+        }
+        actions.push(AppAction::TabOpen(HELP_BUFFER.to_string()));
+        Ok(actions)
+    }
+}
@@ -0,0 +1,290 @@
+mod alerts;
+mod aliases;
+mod app;
+mod help;
+mod orders;
+mod process;
+mod schedule;
+mod tabs;
+
+use actions::AppAction;
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Mutex};
+use structs::app::events;
+use structs::app::{paths, split_word, Aliases, AppState, CommandHandler, Scheduler};
+use utils::shell::Token;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgKind {
+    /// Free text, nothing to complete.
+    Text,
+    /// One of a fixed set of words.
+    Choice(&'static [&'static str]),
+    /// A market, such as `BTC_ETH`.
+    Symbol,
+    /// A running process, possibly abbreviated.
+    Process,
+    /// Any command, running or ended, possibly abbreviated.
+    CommandId,
+    /// A job of the scheduler.
+    JobId,
+    /// An alert set with `:alert`.
+    AlertId,
+    Path,
+    /// The name of a registered command.
+    CommandName,
+    /// The rest of the line is a command of its own.
+    CommandLine,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arg {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub optional: bool,
+    /// Takes every argument left.
+    pub repeated: bool,
+}
+
+impl Arg {
+    pub const fn required(name: &'static str, kind: ArgKind) -> Arg {
+        Arg {
+            name,
+            kind,
+            optional: false,
+            repeated: false,
+        }
+    }
+
+    pub const fn optional(name: &'static str, kind: ArgKind) -> Arg {
+        Arg {
+            name,
+            kind,
+            optional: true,
+            repeated: false,
+        }
+    }
+
+    pub const fn repeated(name: &'static str, kind: ArgKind) -> Arg {
+        Arg {
+            name,
+            kind,
+            optional: false,
+            repeated: true,
+        }
+    }
+}
+
+/// What commands get to work with, besides their arguments.
+pub struct CommandEnv<'a> {
+    pub state: &'a AppState,
+    pub tx: &'a mpsc::Sender<events::Event>,
+    pub handler: &'a CommandHandler,
+    pub scheduler: &'a Scheduler,
+    pub aliases: &'a Mutex<Aliases>,
+    pub registry: &'a CommandRegistry,
+}
+
+/// A single invocation, `args` excludes the command name.
+pub struct CommandCall<'a> {
+    pub uuid: &'a str,
+    /// The line as typed, after alias resolution.
+    pub line: &'a str,
+    pub args: &'a [Token],
+}
+
+impl<'a> CommandCall<'a> {
+    pub fn arg(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(|arg| arg.as_str())
+    }
+}
+
+pub trait CommandSpec: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn args(&self) -> &'static [Arg] {
+        &[]
+    }
+
+    /// One line, shown by `:help`.
+    fn help(&self) -> &'static str;
+
+    /// Complete command lines, shown by `:help NAME`.
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String>;
+
+    fn usage(&self) -> String {
+        let mut usage = String::from(self.name());
+        for arg in self.args() {
+            let name = match arg.repeated {
+                true => format!("{}...", arg.name),
+                false => arg.name.to_string(),
+            };
+            match arg.optional {
+                true => usage.push_str(&format!(" [{}]", name)),
+                false => usage.push_str(&format!(" {}", name)),
+            }
+        }
+        usage
+    }
+
+    /// Checks the number of arguments against `args`.
+    fn validate(&self, call: &CommandCall) -> Result<(), String> {
+        let schema = self.args();
+        let required = schema.iter().filter(|arg| !arg.optional).count();
+        let unbounded = schema.iter().any(|arg| arg.repeated);
+        match call.args.len() {
+            n if n < required || (!unbounded && n > schema.len()) => {
+                Err(format!("Usage: {}", self.usage()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Candidates for the argument after `args`, before filtering by prefix.
+    fn complete(&self, env: &CommandEnv, args: &[&str], word: &str) -> Vec<String> {
+        let schema = self.args();
+        let index = args.len();
+        let arg = match schema.get(index) {
+            Some(arg) => arg,
+            None => match schema.last() {
+                Some(last) if last.repeated || last.kind == ArgKind::CommandLine => last,
+                _ => return Vec::new(),
+            },
+        };
+        match arg.kind {
+            ArgKind::CommandLine => {
+                let start = schema
+                    .iter()
+                    .position(|arg| arg.kind == ArgKind::CommandLine)
+                    .unwrap_or(index);
+                complete(env, &subline(&args[start.min(index)..], word))
+            }
+            kind => candidates(env, kind, word),
+        }
+    }
+}
+
+pub struct CommandRegistry {
+    commands: BTreeMap<&'static str, Box<dyn CommandSpec>>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> CommandRegistry {
+        CommandRegistry {
+            commands: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, command: Box<dyn CommandSpec>) {
+        self.commands.insert(command.name(), command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn CommandSpec> {
+        self.commands.get(name).map(|command| command.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.commands.keys().map(|name| name.to_string()).collect()
+    }
+
+    /// Commands sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn CommandSpec> {
+        self.commands.values().map(|command| command.as_ref())
+    }
+}
+
+impl Default for CommandRegistry {
+    /// Every built-in command.
+    fn default() -> Self {
+        let mut registry = CommandRegistry::new();
+        app::register(&mut registry);
+        process::register(&mut registry);
+        alerts::register(&mut registry);
+        schedule::register(&mut registry);
+        tabs::register(&mut registry);
+        aliases::register(&mut registry);
+        orders::register(&mut registry);
+        help::register(&mut registry);
+        registry
+    }
+}
+
+/// Everything the last word of `line` could be, before filtering.
+pub fn complete(env: &CommandEnv, line: &str) -> Vec<String> {
+    let (base, word) = split_word(line);
+    let words: Vec<&str> = base.split_whitespace().collect();
+    match words.split_first() {
+        None => {
+            let mut names = env.registry.names();
+            names.extend(env.aliases.lock().expect("Aliases Lock Poisoned").names());
+            names
+        }
+        Some((name, args)) => match env.registry.get(name) {
+            Some(command) => command.complete(env, args, word),
+            None => Vec::new(),
+        },
+    }
+}
+
+pub fn candidates(env: &CommandEnv, kind: ArgKind, word: &str) -> Vec<String> {
+    match kind {
+        ArgKind::Text => Vec::new(),
+        ArgKind::Choice(choices) => choices.iter().map(|choice| choice.to_string()).collect(),
+        ArgKind::Symbol => env
+            .state
+            .quotes
+            .keys()
+            .cloned()
+            .chain(env.state.markets.iter().cloned())
+            .collect(),
+        ArgKind::Process => env.handler.running(),
+        ArgKind::CommandId => env
+            .state
+            .cmd_running
+            .iter()
+            .chain(env.state.cmd_ended.iter())
+            .map(|cmd| cmd.id.clone())
+            .collect(),
+        ArgKind::JobId => env
+            .scheduler
+            .jobs()
+            .iter()
+            .map(|job| job.id.to_string())
+            .collect(),
+        ArgKind::AlertId => env
+            .state
+            .alerts
+            .iter()
+            .map(|alert| alert.id.to_string())
+            .collect(),
+        ArgKind::Path => paths(word),
+        ArgKind::CommandName => env.registry.names(),
+        ArgKind::CommandLine => complete(env, word),
+    }
+}
+
+pub fn subline(words: &[&str], word: &str) -> String {
+    let mut words = words.to_vec();
+    words.push(word);
+    words.join(" ")
+}
+
+/// Resolves a (possibly abbreviated) uuid to a command, running or ended.
+pub fn find_command(state: &AppState, uuid: &str) -> Result<String, String> {
+    let matches: Vec<&String> = state
+        .cmd_running
+        .iter()
+        .chain(state.cmd_ended.iter())
+        .map(|cmd| &cmd.id)
+        .filter(|id| id.starts_with(uuid))
+        .collect();
+    match matches.len() {
+        1 => Ok(matches[0].clone()),
+        0 => Err(format!("No command {:?}", uuid)),
+        _ => Err(format!("Ambiguous command id {:?}", uuid)),
+    }
+}
@@ -0,0 +1,118 @@
+use actions::AppAction;
+use commands::{Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use middlewares::keyboard::TICKETMODE;
+use structs::app::{get_quotes, Order, Side, Ticket};
+
+const SIDES: &'static [&'static str] = &["buy", "sell"];
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(TicketCommand {}));
+    registry.register(Box::new(OrderCommand {}));
+}
+
+pub struct TicketCommand {}
+
+impl CommandSpec for TicketCommand {
+    fn name(&self) -> &'static str {
+        "ticket"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[
+            Arg::required("SIDE", ArgKind::Choice(SIDES)),
+            Arg::optional("SYMBOL", ArgKind::Symbol),
+        ];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Fill in an order, for the market selected in the markets pane by default"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["ticket buy", "ticket sell BTC_ETH"]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let side = parse_side(call.arg(0))?;
+        let quotes = get_quotes(env.state);
+        let quote = match call.arg(1) {
+            Some(symbol) => quotes.iter().find(|quote| quote.symbol == symbol),
+            None => quotes.get(env.state.panes.market),
+        };
+        let symbol = call
+            .arg(1)
+            .or_else(|| quote.map(|quote| quote.symbol.as_str()))
+            .unwrap_or("");
+        // priced to cross the spread
+        let price = quote.map(|quote| match side {
+            Side::Buy => quote.ask_price,
+            Side::Sell => quote.bid_price,
+        });
+        let data = serde_json::from_str(TICKETMODE).expect("JSON Error!");
+        Ok(vec![
+            AppAction::SetMode(data),
+            AppAction::TicketSet(Some(Ticket::new(side, symbol, price))),
+        ])
+    }
+}
+
+pub struct OrderCommand {}
+
+impl CommandSpec for OrderCommand {
+    fn name(&self) -> &'static str {
+        "order"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[
+            Arg::required("SIDE", ArgKind::Choice(SIDES)),
+            Arg::required("SYMBOL", ArgKind::Symbol),
+            Arg::required("AMOUNT", ArgKind::Text),
+            Arg::required("PRICE", ArgKind::Text),
+        ];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Log a paper order, define a macro named order to send it somewhere"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "order buy BTC_ETH 1.5 0.0321",
+            "macro order = exec ./place_order.sh $1 $2 $3 $4",
+        ]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let side = parse_side(call.arg(0))?;
+        let amount = parse_number(call.arg(2), "amount")?;
+        let price = parse_number(call.arg(3), "price")?;
+        let order = Order::new(side, call.arg(1).unwrap_or(""), amount, price)?;
+        env.state.risk.check(&order)?;
+        Ok(vec![AppAction::ConsolePush(format_output!(
+            "green",
+            "ORDER",
+            format!(
+                "{} {} {} @ {} (paper)",
+                order.side.name().to_uppercase(),
+                order.amount,
+                order.symbol,
+                order.price
+            )
+        ))])
+    }
+}
+
+fn parse_side(side: Option<&str>) -> Result<Side, String> {
+    let side = side.unwrap_or("");
+    Side::parse(side).ok_or_else(|| format!("Invalid side {:?}, buy or sell", side))
+}
+
+fn parse_number(value: Option<&str>, name: &str) -> Result<f64, String> {
+    value
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .ok_or_else(|| format!("Invalid {}", name))
+}
@@ -0,0 +1,156 @@
+use actions::AppAction;
+use commands::{Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use middlewares::keyboard::INPUTMODE;
+use structs::app::Pipeline;
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(ExecCommand {}));
+    registry.register(Box::new(KillCommand {}));
+    registry.register(Box::new(SignalCommand {}));
+    registry.register(Box::new(WaitCommand {}));
+    registry.register(Box::new(FocusCommand {}));
+}
+
+pub struct ExecCommand {}
+
+impl CommandSpec for ExecCommand {
+    fn name(&self) -> &'static str {
+        "exec"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::repeated("PIPELINE", ArgKind::Path)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Run a program, with | pipes and < > >> redirections"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["exec ls -l", "exec grep BTC < trades.csv | sort >> btc.txt"]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let pipeline = Pipeline::parse(call.args)?;
+        env.handler
+            .spawn(env.tx.clone(), pipeline, call.uuid.to_string());
+        Ok(vec![AppAction::CommandCreate(call.uuid.to_string())])
+    }
+}
+
+pub struct KillCommand {}
+
+impl CommandSpec for KillCommand {
+    fn name(&self) -> &'static str {
+        "kill"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::required("UUID", ArgKind::Process)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Kill a running process"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let target = env.handler.kill(call.arg(0).unwrap_or(""))?;
+        Ok(vec![sent(&target, call)])
+    }
+}
+
+pub struct SignalCommand {}
+
+impl CommandSpec for SignalCommand {
+    fn name(&self) -> &'static str {
+        "signal"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[
+            Arg::required("UUID", ArgKind::Process),
+            Arg::required("SIGNAL", ArgKind::Choice(&["TERM", "INT"])),
+        ];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Send TERM or INT to a running process"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["signal 1a2b TERM"]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let target = env
+            .handler
+            .signal(call.arg(0).unwrap_or(""), call.arg(1).unwrap_or(""))?;
+        Ok(vec![sent(&target, call)])
+    }
+}
+
+fn sent(target: &str, call: &CommandCall) -> AppAction {
+    AppAction::ConsolePush(format_output!(
+        "yellow",
+        target,
+        format!("Sent {}", call.line.trim())
+    ))
+}
+
+pub struct WaitCommand {}
+
+impl CommandSpec for WaitCommand {
+    fn name(&self) -> &'static str {
+        "wait"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::required("UUID", ArgKind::Process)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Run until a process has ended"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        env.handler
+            .wait(call.arg(0).unwrap_or(""), call.uuid.to_string())?;
+        Ok(vec![AppAction::CommandCreate(call.uuid.to_string())])
+    }
+}
+
+pub struct FocusCommand {}
+
+impl CommandSpec for FocusCommand {
+    fn name(&self) -> &'static str {
+        "focus"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::optional("UUID", ArgKind::Process)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Type into a process' stdin, the one of the current tab by default"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let target = match (call.arg(0), env.state.tabs.selected_uuid()) {
+            (Some(target), _) => target.to_string(),
+            (None, Some(selected)) => selected.clone(),
+            (None, None) => String::new(),
+        };
+        let target = env.handler.find(&target)?;
+        let data = serde_json::from_str(INPUTMODE).expect("JSON Error!");
+        Ok(vec![
+            AppAction::ConsolePush(format_output!("green", target, "Focused, Esc to leave")),
+            AppAction::SetMode(data),
+            AppAction::CommandFocus(Some(target)),
+        ])
+    }
+}
@@ -0,0 +1,143 @@
+use actions::AppAction;
+use commands::{Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+use structs::app::Schedule;
+use utils::shell;
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(EveryCommand {}));
+    registry.register(Box::new(AtCommand {}));
+    registry.register(Box::new(JobsCommand {}));
+    registry.register(Box::new(UnscheduleCommand {}));
+}
+
+// The command is kept as typed, so variables and `~` are expanded on each run.
+fn schedule(env: &CommandEnv, schedule: Schedule, call: &CommandCall) -> Vec<AppAction> {
+    let scheduled = shell::rest(call.line, 2).trim_end();
+    let job = env
+        .scheduler
+        .add(schedule, scheduled.trim_start_matches(':').to_string());
+    vec![AppAction::ConsolePush(format_output!(
+        "green",
+        format!("JOB {}", job.id),
+        job.describe()
+    ))]
+}
+
+pub struct EveryCommand {}
+
+impl CommandSpec for EveryCommand {
+    fn name(&self) -> &'static str {
+        "every"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[
+            Arg::required("INTERVAL", ArgKind::Text),
+            Arg::repeated("COMMAND", ArgKind::CommandLine),
+        ];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Run a command repeatedly, every 30s, 5m, 2h, 1d..."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["every 5m exec ./snapshot.sh"]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let every = Schedule::parse_every(call.arg(0).unwrap_or(""))?;
+        Ok(schedule(env, every, call))
+    }
+}
+
+pub struct AtCommand {}
+
+impl CommandSpec for AtCommand {
+    fn name(&self) -> &'static str {
+        "at"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[
+            Arg::required("HH:MM", ArgKind::Text),
+            Arg::repeated("COMMAND", ArgKind::CommandLine),
+        ];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Run a command once, at the next HH:MM local time"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["at 09:30 exec ./open.sh"]
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let at = Schedule::parse_at(call.arg(0).unwrap_or(""))?;
+        Ok(schedule(env, at, call))
+    }
+}
+
+pub struct JobsCommand {}
+
+impl CommandSpec for JobsCommand {
+    fn name(&self) -> &'static str {
+        "jobs"
+    }
+
+    fn help(&self) -> &'static str {
+        "List scheduled commands"
+    }
+
+    fn execute(&self, env: &CommandEnv, _call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let jobs = env.scheduler.jobs();
+        let mut lines = vec![AppAction::ConsolePush(format_output!(
+            "white",
+            "Jobs",
+            format!("{} scheduled", jobs.len())
+        ))];
+        for job in jobs {
+            lines.push(AppAction::ConsolePush(format_output!(
+                "white",
+                job.id,
+                job.describe()
+            )));
+        }
+        Ok(lines)
+    }
+}
+
+pub struct UnscheduleCommand {}
+
+impl CommandSpec for UnscheduleCommand {
+    fn name(&self) -> &'static str {
+        "unschedule"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::required("JOB", ArgKind::JobId)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Remove a scheduled command"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let removed = call
+            .arg(0)
+            .and_then(|id| id.parse::<usize>().ok())
+            .and_then(|id| env.scheduler.remove(id));
+        match removed {
+            Some(job) => Ok(vec![AppAction::ConsolePush(format_output!(
+                "green",
+                format!("JOB {}", job.id),
+                "Unscheduled"
+            ))]),
+            None => Err(String::from("No such job")),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use actions::AppAction;
+use commands::{find_command, Arg, ArgKind, CommandCall, CommandEnv, CommandRegistry, CommandSpec};
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry.register(Box::new(TabCommand {}));
+    registry.register(Box::new(TabCloseCommand {}));
+}
+
+pub struct TabCommand {}
+
+impl CommandSpec for TabCommand {
+    fn name(&self) -> &'static str {
+        "tab"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::required("UUID", ArgKind::CommandId)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Show the output of a command in a tab of its own"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let target = find_command(env.state, call.arg(0).unwrap_or(""))?;
+        Ok(vec![AppAction::TabOpen(target)])
+    }
+}
+
+pub struct TabCloseCommand {}
+
+impl CommandSpec for TabCloseCommand {
+    fn name(&self) -> &'static str {
+        "tabclose"
+    }
+
+    fn args(&self) -> &'static [Arg] {
+        const ARGS: &'static [Arg] = &[Arg::optional("INDEX", ArgKind::Text)];
+        ARGS
+    }
+
+    fn help(&self) -> &'static str {
+        "Close a command tab, the current one by default"
+    }
+
+    fn execute(&self, env: &CommandEnv, call: &CommandCall) -> Result<Vec<AppAction>, String> {
+        let tabs = &env.state.tabs;
+        let index = match call.arg(0) {
+            Some(index) => index
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid tab {:?}", index))?,
+            None => tabs.selection,
+        };
+        if index == 0 || index >= tabs.titles.len() {
+            return Err(String::from("No such command tab"));
+        }
+        Ok(vec![AppAction::TabClose(index)])
+    }
+}
@@ -1,17 +1,27 @@
 use components::command_bar;
 use components::command_output;
+use components::completion;
 use components::ele::powerline_tab::Tabs;
+use components::markets;
+use components::palette;
 use components::status_bar;
+use components::ticket;
 use structs::app::AppState;
 use tui::backend::Backend;
-use tui::layout::{Constraint, Direction, Layout};
+use tui::layout::{Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Style};
 use tui::Frame;
 
-pub fn render<B>(frame: &mut Frame<B>, store: &AppState)
-where
-    B: Backend,
-{
+/// Where each part of the screen goes, shared with the mouse middleware.
+pub struct Areas {
+    pub tabs: Rect,
+    pub output: Rect,
+    pub markets: Option<Rect>,
+    pub status: Rect,
+    pub command: Rect,
+}
+
+pub fn areas(size: Rect, store: &AppState) -> Areas {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
@@ -23,23 +33,61 @@ where
             ]
             .as_ref(),
         )
-        .split(frame.size());
+        .split(size);
+
+    // the markets pane only shows up once quotes stream in
+    let main = match store.quotes.is_empty() {
+        true => vec![chunks[1]],
+        false => Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(1), Constraint::Length(markets::WIDTH)].as_ref())
+            .split(chunks[1]),
+    };
+
+    Areas {
+        tabs: chunks[0],
+        output: main[0],
+        markets: main.get(1).cloned(),
+        status: chunks[2],
+        command: chunks[3],
+    }
+}
 
-    let tabs = Tabs::default()
+pub fn tabs(store: &AppState) -> Tabs<String> {
+    Tabs::default()
         .titles(&store.tabs.titles)
         .style(Style::default().fg(Color::Gray).bg(Color::Black))
         .highlight_style(Style::default().fg(Color::Black).bg(Color::White))
         .divider_style(Style::default().fg(Color::White).bg(Color::Black))
-        .select(store.tabs.selection);
+        .select(store.tabs.selection)
+}
+
+pub fn render<B>(frame: &mut Frame<B>, store: &AppState)
+where
+    B: Backend,
+{
+    let areas = areas(frame.size(), store);
+    // overlays float over the output and markets panes
+    let main = areas
+        .markets
+        .map_or(areas.output, |markets| areas.output.union(markets));
 
-    frame.render_widget(tabs, chunks[0]);
+    frame.render_widget(tabs(store), areas.tabs);
 
-    match store.tabs.selection {
-        0 => command_output::render(frame, store, chunks[1]),
-        1 => {}
-        _ => {}
+    command_output::render(
+        frame,
+        &store.output_lines(),
+        store.tabs.selected_scroll(),
+        store.selection,
+        areas.output,
+    );
+    if let Some(area) = areas.markets {
+        markets::render(frame, store, area);
     }
+    completion::render(frame, store, main);
+    palette::render(frame, store, main);
+    ticket::render(frame, store, main);
 
-    status_bar::render(frame, store, chunks[2]);
-    command_bar::render(frame, store, chunks[3]);
+    status_bar::render(frame, store, areas.status);
+    command_bar::render(frame, store, areas.command);
 }
@@ -8,17 +8,43 @@ use structs::app::AppState;
 
 const DATA: &'static str = r#"
 <Paragraph>
-    <Spans>{{store.command}}</Spans>
+    <Spans>
+        <Span>{{before}}</Span>
+        {{#if cursor ~}}
+            <Span styles='{"modifier": "reversed"}'>{{cursor}}</Span>
+        {{~/if}}
+        <Span>{{after}}</Span>
+    </Spans>
 </Paragraph>"#;
 
 pub fn render<B>(frame: &mut Frame<B>, store: &AppState, area: Rect)
 where
     B: Backend,
 {
+    let line: Vec<char> = store.json_store["command"]
+        .as_str()
+        .expect("command is not str")
+        .chars()
+        .collect();
+    let cursor = store.cmd_cursor.min(line.len());
+    // scroll long lines so the cursor stays in view
+    let offset = (cursor + 1).saturating_sub(area.width as usize);
+    let before: String = line[offset..cursor].iter().collect();
+    // the cursor is only shown while there is a line being edited, a
+    // whitespace-only text node would be dropped by the XML parser
+    let at_cursor = match (line.is_empty(), line.get(cursor)) {
+        (true, _) => String::new(),
+        (false, Some(c)) if !c.is_whitespace() => c.to_string(),
+        (false, _) => String::from("\u{a0}"),
+    };
+    let after: String = line.iter().skip(cursor + 1).collect();
+
     let dom_root = xml::parse(
         DATA.to_string(),
         &json!({
-            "store": &store.json_store,
+            "before": before,
+            "cursor": at_cursor,
+            "after": after,
             "metrics": {
                 "height": area.height,
                 "width": area.width
@@ -8,21 +8,51 @@ use tui::widgets::{Paragraph, Wrap};
 use tui::Frame;
 
 use components::xml;
-use structs::app::AppState;
+use structs::ui::{OutputBuffer, Selection};
 
 const DATA: &'static str = r#"
 <Paragraph styles='{"wrap": {"trim": "true"}, "block": "default"}' scroll='true'>
-    {{#each store.console_output_lines as |line| ~}}
+    {{#each lines as |line| ~}}
         <Spans>
-            <Span>{{line}}</Span>
+            {{#if line.text ~}}
+                <Span styles='{"fg": "{{line.fg}}", "bg": "{{line.bg}}"}'>{{line.text}}</Span>
+            {{~else ~}}
+                <Span>{{line}}</Span>
+            {{~/if}}
         </Spans>
     {{/each}}
 </Paragraph>"#;
 
-pub fn render<B>(frame: &mut Frame<B>, store: &AppState, area: Rect)
-where
+/// Renders `lines`, either the console or the output of a single command,
+/// scrolled back `offset` lines from the end, the `selection` reversed.
+pub fn render<B>(
+    frame: &mut Frame<B>,
+    lines: &OutputBuffer,
+    offset: usize,
+    selection: Option<Selection>,
+    area: Rect,
+) where
     B: Backend,
 {
+    let end = lines.len() - offset.min(lines.len());
+    let start = end.saturating_sub(area.height as usize);
+    let lines: Vec<Value> = lines
+        .range(start, end)
+        .enumerate()
+        .map(|(index, line)| match selection {
+            Some(selection) if selection.contains(start + index) => {
+                let text = line
+                    .as_str()
+                    .or_else(|| line["text"].as_str())
+                    .unwrap_or("");
+                // an empty text would take the plain branch of the template
+                let text = if text.is_empty() { " " } else { text };
+                json!({ "text": text, "fg": "black", "bg": "white" })
+            }
+            _ => line.clone(),
+        })
+        .collect();
+
     // let array = store.json_store["console_output_lines"].as_array().expect("Data Error");
 
     // let buf = get_buffer(
@@ -33,7 +63,7 @@ where
     let dom_root = xml::parse(
         DATA.to_string(),
         &json!({
-            "store": &store.json_store,
+            "lines": lines,
             "metrics": {
                 "height": area.height,
                 "width": area.width
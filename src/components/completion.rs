@@ -0,0 +1,51 @@
+use structs::app::AppState;
+use tui::backend::Backend;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+use tui::Frame;
+
+const MAX_ROWS: u16 = 8;
+
+/// Candidates popup, drawn over the bottom of `area` under the completed word.
+pub fn render<B>(frame: &mut Frame<B>, store: &AppState, area: Rect)
+where
+    B: Backend,
+{
+    let completion = match store.completion {
+        Some(ref completion) if !completion.candidates.is_empty() => completion,
+        _ => return,
+    };
+    let widest = completion
+        .candidates
+        .iter()
+        .map(|candidate| candidate.chars().count())
+        .max()
+        .unwrap_or(0) as u16;
+    let width = (widest + 2).min(area.width);
+    let height = (completion.candidates.len() as u16).min(MAX_ROWS) + 2;
+    let height = height.min(area.height);
+    // the bar starts with `:`
+    let column = completion.base.chars().count() as u16 + 1;
+    let popup = Rect {
+        x: area.x + column.min(area.width - width),
+        y: area.y + area.height - height,
+        width,
+        height,
+    };
+
+    let items: Vec<ListItem> = completion
+        .candidates
+        .iter()
+        .map(|candidate| ListItem::new(candidate.as_str()))
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::White));
+    let mut list_state = ListState::default();
+    list_state.select(completion.selected);
+
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(list, popup, &mut list_state);
+}
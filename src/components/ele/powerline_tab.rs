@@ -102,6 +102,26 @@ where
     }
 }
 
+impl<'a, T> Tabs<'a, T>
+where
+    T: AsRef<str>,
+{
+    /// Index of the title drawn `x` columns right of the left of the tabs,
+    /// its divider included.
+    pub fn title_at(&self, x: u16) -> Option<usize> {
+        let title_padding: u16 = 2;
+        let divider_width = self.divider.width() as u16;
+        let mut right = 0;
+        for (i, title) in self.titles.iter().enumerate() {
+            right += title.as_ref().width() as u16 + title_padding + divider_width + 1;
+            if x < right {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
 impl<'a, T> Widget for Tabs<'a, T>
 where
     T: AsRef<str>,
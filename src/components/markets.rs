@@ -0,0 +1,73 @@
+use structs::app::{get_quotes, AppState, Side};
+use structs::ui::Pane;
+use tui::backend::Backend;
+use tui::layout::Rect;
+use tui::style::{Color, Modifier, Style};
+use tui::text::Span;
+use tui::widgets::{Block, Borders, List, ListItem, ListState};
+use tui::Frame;
+
+pub const WIDTH: u16 = 36;
+// where the bid and ask columns start, right of the border
+const BID_COLUMN: u16 = 11;
+const ASK_COLUMN: u16 = 23;
+
+/// Quotes side pane, the selected row highlighted while focused.
+pub fn render<B>(frame: &mut Frame<B>, store: &AppState, area: Rect)
+where
+    B: Backend,
+{
+    let focused = store.panes.focused == Pane::Markets;
+    let title_style = match focused {
+        true => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        false => Style::default().fg(Color::Gray),
+    };
+    let items: Vec<ListItem> = get_quotes(store)
+        .iter()
+        .map(|quote| {
+            ListItem::new(format!(
+                "{:<10} {:>11.8} {:>11.8}",
+                quote.symbol, quote.bid_price, quote.ask_price
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::LEFT)
+                .title(Span::styled(" Markets ", title_style)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .highlight_style(match focused {
+            true => Style::default().fg(Color::Black).bg(Color::White),
+            false => Style::default().add_modifier(Modifier::BOLD),
+        });
+    let mut list_state = ListState::default();
+    list_state.select(Some(store.panes.market));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Row of the quote drawn at `x`, `y` within `area`, with the side a click
+/// on its bid (sell) or ask (buy) column takes.
+pub fn row_at(store: &AppState, area: Rect, x: u16, y: u16) -> Option<(usize, Option<Side>)> {
+    // the left border and the title take a column and a row
+    let (left, top) = (area.x + 1, area.y + 1);
+    if x < left || y < top || x >= area.right() || y >= area.bottom() {
+        return None;
+    }
+    // the list scrolls just enough to show the selected row
+    let height = (area.bottom() - top) as usize;
+    let offset = store.panes.market.saturating_sub(height.saturating_sub(1));
+    let row = offset + (y - top) as usize;
+    if row >= store.quotes.len() {
+        return None;
+    }
+    let side = match x - left {
+        column if column >= ASK_COLUMN => Some(Side::Buy),
+        column if column >= BID_COLUMN => Some(Side::Sell),
+        _ => None,
+    };
+    Some((row, side))
+}
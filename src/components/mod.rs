@@ -1,8 +1,12 @@
 pub mod app;
 pub mod command_bar;
 pub mod command_output;
+pub mod completion;
 pub mod containers;
 pub mod ele;
 pub mod element;
+pub mod markets;
+pub mod palette;
 pub mod status_bar;
+pub mod ticket;
 pub mod xml;
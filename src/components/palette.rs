@@ -0,0 +1,56 @@
+use structs::app::AppState;
+use tui::backend::Backend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use tui::Frame;
+
+/// Command palette, centered over `area`.
+pub fn render<B>(frame: &mut Frame<B>, store: &AppState, area: Rect)
+where
+    B: Backend,
+{
+    let palette = match store.palette {
+        Some(ref palette) => palette,
+        None => return,
+    };
+    let width = (area.width * 3 / 4).max(20).min(area.width);
+    let height = (area.height * 3 / 4).max(5).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default().title(" Commands ").borders(Borders::ALL);
+    let inner = block.inner(popup);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+        .split(inner);
+
+    let query = Paragraph::new(Spans::from(vec![
+        Span::raw("> "),
+        Span::raw(palette.query.as_str()),
+        Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+    ]));
+    let items: Vec<ListItem> = palette
+        .matches()
+        .into_iter()
+        .map(|entry| {
+            ListItem::new(Spans::from(vec![
+                Span::raw(format!("{:<32} ", entry.label)),
+                Span::styled(entry.detail.as_str(), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(Style::default().fg(Color::Black).bg(Color::White));
+    let mut list_state = ListState::default();
+    list_state.select(Some(palette.selected));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
+    frame.render_widget(query, chunks[0]);
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+}
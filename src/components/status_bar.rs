@@ -1,4 +1,4 @@
-use structs::app::AppState;
+use structs::app::{keys_name, AppState};
 use tui::backend::Backend;
 use tui::layout::Rect;
 use tui::style::{Color, Style};
@@ -14,7 +14,7 @@ where
     let value = store.json_store["mode"]["symbol"]
         .as_str()
         .expect("JSON Error");
-    let text = Spans::from(vec![
+    let mut spans = vec![
         Span::styled(" ", Style::default().fg(Color::White).bg(Color::Black)),
         Span::styled(value, Style::default().bg(Color::Black)),
         Span::styled(" ", Style::default().fg(Color::White).bg(Color::Black)),
@@ -31,7 +31,37 @@ where
             "\u{E0B0}",
             Style::default().fg(Color::White).bg(Color::Black),
         ),
-    ]);
-    let paragraph = Paragraph::new(text);
+    ];
+    let scroll = store.tabs.selected_scroll();
+    if scroll > 0 {
+        spans.push(Span::styled(
+            format!(" +{} ", scroll),
+            Style::default().fg(Color::Cyan).bg(Color::Black),
+        ));
+    }
+    let dropped = store.output_lines().dropped();
+    if dropped > 0 {
+        spans.push(Span::styled(
+            format!(" {} dropped ", dropped),
+            Style::default().fg(Color::Gray).bg(Color::Black),
+        ));
+    }
+    if !store.pending_keys.is_empty() {
+        spans.push(Span::styled(
+            format!(" {} ", keys_name(&store.pending_keys)),
+            Style::default().fg(Color::Yellow).bg(Color::Black),
+        ));
+    }
+    if let Some((id, ref alert)) = store.alert_flash {
+        spans.push(Span::styled(
+            format!(" ALERT {}: {} ", id, alert),
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ));
+        spans.push(Span::styled(
+            "\u{E0B0}",
+            Style::default().fg(Color::Yellow).bg(Color::Black),
+        ));
+    }
+    let paragraph = Paragraph::new(Spans::from(spans));
     frame.render_widget(paragraph, area);
 }
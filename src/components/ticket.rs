@@ -0,0 +1,73 @@
+use structs::app::{AppState, Side, TICKET_FIELDS};
+use tui::backend::Backend;
+use tui::layout::Rect;
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, Paragraph};
+use tui::Frame;
+
+const WIDTH: u16 = 40;
+
+/// Order ticket, centered over `area` while in ticket mode.
+pub fn render<B>(frame: &mut Frame<B>, store: &AppState, area: Rect)
+where
+    B: Backend,
+{
+    let ticket = match store.ticket {
+        Some(ref ticket) => ticket,
+        None => return,
+    };
+    // fields, a blank line, then the error or the hints
+    let height = (TICKET_FIELDS.len() as u16 + 4).min(area.height);
+    let width = WIDTH.min(area.width);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let color = match ticket.side {
+        Side::Buy => Color::Green,
+        Side::Sell => Color::Red,
+    };
+    let title = Span::styled(
+        format!(" {} ", ticket.side.name().to_uppercase()),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    );
+
+    let mut lines: Vec<Spans> = TICKET_FIELDS
+        .iter()
+        .zip(ticket.values.iter())
+        .enumerate()
+        .map(|(index, (name, value))| {
+            let cursor = match index == ticket.selected {
+                true => Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+                false => Span::raw(""),
+            };
+            Spans::from(vec![
+                Span::styled(format!("{:>8} ", name), Style::default().fg(Color::Gray)),
+                Span::raw(value.clone()),
+                cursor,
+            ])
+        })
+        .collect();
+    lines.push(Spans::from(""));
+    lines.push(match ticket.error {
+        Some(ref error) => {
+            Spans::from(Span::styled(error.clone(), Style::default().fg(Color::Red)))
+        }
+        None => Spans::from(Span::styled(
+            "Enter place, C-t side, Esc cancel",
+            Style::default().fg(Color::Gray),
+        )),
+    });
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color)),
+    );
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(paragraph, popup);
+}
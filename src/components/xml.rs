@@ -1,6 +1,7 @@
 use handlebars::{handlebars_helper, Handlebars};
 use serde_json::Value;
 use treexml::{Document, Element};
+use tui::style::{Color, Modifier, Style};
 use tui::text::{Span, Spans};
 use tui::widgets::Block;
 use tui::widgets::Paragraph;
@@ -96,10 +97,53 @@ pub fn create_element(el: Element) -> El {
                 Some(txt) => txt,
                 None => String::from(""),
             };
-            El::Span(Span::from(text))
+            match styles {
+                Some(ref style) => El::Span(Span::styled(text, create_style(style))),
+                None => El::Span(Span::from(text)),
+            }
         }
         &_ => panic!("Unknown DOM Token"),
     };
 
     this
 }
+
+// styles='{"fg": "yellow", "bg": "black", "modifier": "bold"}'
+fn create_style(styles: &Value) -> Style {
+    let mut style = Style::default();
+    if let Some(color) = styles["fg"].as_str() {
+        style = style.fg(parse_color(color));
+    }
+    if let Some(color) = styles["bg"].as_str() {
+        style = style.bg(parse_color(color));
+    }
+    if let Some(modifier) = styles["modifier"].as_str() {
+        style = style.add_modifier(parse_modifier(modifier));
+    }
+    style
+}
+
+fn parse_color(color: &str) -> Color {
+    match color {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "white" => Color::White,
+        &_ => Color::Reset,
+    }
+}
+
+fn parse_modifier(modifier: &str) -> Modifier {
+    match modifier {
+        "bold" => Modifier::BOLD,
+        "italic" => Modifier::ITALIC,
+        "underlined" => Modifier::UNDERLINED,
+        "reversed" => Modifier::REVERSED,
+        &_ => Modifier::empty(),
+    }
+}
@@ -1,8 +1,11 @@
 #[macro_use]
 extern crate log;
 
-// extern crate cpython;
+extern crate chrono;
+extern crate cpython;
 extern crate handlebars;
+extern crate libc;
+extern crate libloading;
 extern crate redux;
 extern crate regex;
 extern crate serde;
@@ -13,13 +16,16 @@ extern crate treexml;
 extern crate tui;
 extern crate unicode_width;
 extern crate uuid;
+extern crate ws;
 
 #[macro_use]
 mod utils;
 mod actions;
+mod commands;
 mod components;
 mod middlewares;
 mod reducers;
+mod strategies;
 mod structs;
 
 use simplelog::*;
@@ -45,7 +51,10 @@ fn main() -> Result<(), io::Error> {
     let (input_tx, subscribe_tx) = (cmd_tx.clone(), tx.clone());
 
     let _ = utils::input::init(input_tx);
-    let store = utils::store::init(&cmd_tx);
+    utils::input::watch_size(cmd_tx.clone());
+    let settings = utils::store::init_settings(&cmd_tx);
+    utils::market::connect(cmd_tx.clone(), settings.feed(), &settings.markets());
+    let store = utils::store::init(&cmd_tx, &settings);
 
     // Create Subscription from store to render
     store.subscribe(Box::new(move |store, _| {
@@ -54,5 +63,5 @@ fn main() -> Result<(), io::Error> {
     }));
 
     utils::commands::connect(cmd_rx, store, tx);
-    utils::run::keep_alive(rx)
+    utils::run::keep_alive(rx, settings.frame_interval())
 }
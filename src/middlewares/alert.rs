@@ -0,0 +1,58 @@
+use actions::AppAction;
+use redux::{DispatchFunc, Middleware, Store};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use structs::app::events;
+use structs::app::AppState;
+use uuid::Uuid;
+
+const FLASH_SECS: u64 = 3;
+
+pub struct AlertMiddleWare {
+    pub tx: mpsc::Sender<events::Event>,
+}
+
+impl Middleware<AppState> for AlertMiddleWare {
+    fn dispatch(
+        &self,
+        store: &Store<AppState>,
+        action: AppAction,
+        next: &DispatchFunc<AppState>,
+    ) -> Result<AppState, String> {
+        debug!("7 {:?}", &action);
+        if let AppAction::QuoteUpdate(ref quote) = action {
+            let state = store.get_state();
+            for alert in state.alerts.iter().filter(|a| a.evaluate(quote)) {
+                let _ = store.dispatch(AppAction::AlertTrigger(alert.id));
+                // evaluated, so there is a value
+                let value = alert.value(quote).unwrap_or_default();
+                let line = format_output!(
+                    "yellow",
+                    format!("ALERT {}", alert.id),
+                    format!("{} ({})", alert.describe(), value)
+                );
+                let _ = store.dispatch(AppAction::ConsoleHighlight {
+                    line,
+                    color: String::from("yellow"),
+                });
+                if let Some(ref process) = alert.exec {
+                    // a process, as the keyword says, rather than a `:` command
+                    let evt = AppAction::CommandEnqueue {
+                        uuid: Uuid::new_v4().to_string(),
+                        cmd_str: format!("exec {}", process),
+                    }
+                    .into_event();
+                    let _ = self.tx.send(evt);
+                }
+                let flash_tx = self.tx.clone();
+                let id = alert.id;
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_secs(FLASH_SECS));
+                    let _ = flash_tx.send(AppAction::AlertFlashClear(id).into_event());
+                });
+            }
+        }
+        next(store, action)
+    }
+}
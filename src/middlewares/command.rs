@@ -1,12 +1,21 @@
 use actions::AppAction;
+use commands::{self, CommandCall, CommandEnv, CommandRegistry};
+use middlewares::keyboard::{key_help, NORMALMODE};
 use redux::{DispatchFunc, Middleware, Store};
-use std::sync::mpsc;
+use std::sync::{mpsc, Mutex};
 use structs::app::events;
-use structs::app::{AppState, CommandHandler};
+use structs::app::{
+    split_word, Aliases, AppState, CommandHandler, Completion, Palette, PaletteEntry, Scheduler,
+};
+use utils::shell::{self, Token};
+use uuid::Uuid;
 
 pub struct CommandMiddleWare {
     pub tx: mpsc::Sender<events::Event>,
     pub handler: CommandHandler,
+    pub scheduler: Scheduler,
+    pub aliases: Mutex<Aliases>,
+    pub registry: CommandRegistry,
 }
 
 impl Middleware<AppState> for CommandMiddleWare {
@@ -18,7 +27,8 @@ impl Middleware<AppState> for CommandMiddleWare {
     ) -> Result<AppState, String> {
         debug!("5 {:?}", &action);
         match action {
-            AppAction::CommandBarEnqueueCmd(ref uuid) => {
+            AppAction::CommandBarEnqueueCmd(ref uuid)
+            | AppAction::CommandEnqueue { ref uuid, .. } => {
                 let evt = AppAction::CommandConsume(uuid.to_string()).into_event();
                 self.tx.send(evt).expect("Failed to Send");
             }
@@ -26,44 +36,183 @@ impl Middleware<AppState> for CommandMiddleWare {
                 let state = store.get_state();
                 match state.cmd_str_queue.get(uuid) {
                     Some(command) => {
-                        let mut cmd_with_args: Vec<&str> = command.split(' ').collect();
-                        let cmd_str = cmd_with_args.remove(0);
-                        // if command registry contains "exec"
-                        // currently the only key
-                        debug!("CMD STR {:?}", cmd_str);
-                        let _action = match self.handler.cmd_reg.contains_key(cmd_str) {
-                            true => match cmd_str {
-                                "exec" => {
-                                    debug!("EXIT SIGNAL SENT 1");
-                                    self.handler.spawn(
-                                        self.tx.clone(),
-                                        cmd_with_args.join(" "),
-                                        uuid.to_string(),
-                                    );
-                                    AppAction::CommandCreate(uuid.to_string())
-                                }
-                                "q" => {
-                                    self.tx.send(events::Event::Exit).expect("Failed to Send");
-                                    AppAction::ConsolePush("Exiting...".to_string())
-                                }
-                                &_ => AppAction::CommandInvalid(uuid.to_string()),
-                            },
-                            false => AppAction::CommandInvalid(uuid.to_string()),
+                        let resolved = self
+                            .aliases
+                            .lock()
+                            .expect("Aliases Lock Poisoned")
+                            .resolve(command);
+                        let actions = resolved.and_then(|command| {
+                            let tokens = shell::tokenize(&command)?;
+                            self.run(&state, uuid, &command, &tokens)
+                        });
+                        let actions = match actions {
+                            Ok(actions) => actions,
+                            Err(reason) => vec![AppAction::CommandInvalid {
+                                uuid: uuid.to_string(),
+                                reason,
+                            }],
                         };
-                        let _ = store.dispatch(_action);
+                        for _action in actions {
+                            let _ = store.dispatch(_action);
+                        }
                     }
                     None => {
                         debug!("No Command in Queue{:?}", uuid);
                     }
                 }
             }
+            AppAction::CommandBarComplete => {
+                let state = store.get_state();
+                let line: String = state.json_store["command"]
+                    .as_str()
+                    .expect("command is not str")
+                    .chars()
+                    .skip(1)
+                    .collect();
+                let (base, word) = split_word(&line);
+                let candidates = commands::complete(&self.env(&state), &line);
+                let completion = Completion::new(base, word, candidates);
+                match completion.candidates.len() {
+                    0 => {}
+                    1 => {
+                        let candidate = &completion.candidates[0];
+                        // directories are likely to be completed further
+                        let space = if candidate.ends_with('/') { "" } else { " " };
+                        let line = format!(":{}{}", completion.line_with(candidate), space);
+                        let _ = store.dispatch(AppAction::CommandBarSet(line));
+                    }
+                    _ => {
+                        let line = completion.line_with(&completion.common_prefix());
+                        let _ = store.dispatch(AppAction::CommandBarSet(format!(":{}", line)));
+                        let _ = store.dispatch(AppAction::CompletionSet(Some(completion)));
+                    }
+                }
+            }
+            AppAction::PaletteOpen => {
+                let state = store.get_state();
+                let mut entries: Vec<PaletteEntry> = self
+                    .registry
+                    .iter()
+                    .map(|command| PaletteEntry {
+                        label: command.usage(),
+                        detail: command.help().to_string(),
+                        command: Some(command.name().to_string()),
+                    })
+                    .collect();
+                entries.extend(key_help(&state.keymap).into_iter().map(
+                    |(mode, keys, description)| PaletteEntry {
+                        label: keys,
+                        detail: format!("{} ({})", description, mode),
+                        command: None,
+                    },
+                ));
+                let _ = store.dispatch(AppAction::PaletteSet(Some(Palette::new(entries))));
+            }
+            AppAction::TicketSubmit => {
+                // an invalid ticket stays open, the reducer keeps the error
+                let state = store.get_state();
+                let order = state
+                    .ticket
+                    .as_ref()
+                    .map(|ticket| ticket.order(&state.risk));
+                if let Some(Ok(order)) = order {
+                    let data = serde_json::from_str(NORMALMODE).expect("JSON Error!");
+                    let _ = store.dispatch(AppAction::SetMode(data));
+                    let _ = store.dispatch(AppAction::CommandEnqueue {
+                        uuid: Uuid::new_v4().to_string(),
+                        cmd_str: order.command(),
+                    });
+                }
+            }
+            AppAction::StdinSend { ref uuid, ref line } => {
+                let _ = match self.handler.write_stdin(uuid, line) {
+                    Ok(_) => {
+                        let line = format_output!("white", "<<<", line);
+                        store.dispatch(AppAction::ConsolePush(line))
+                    }
+                    Err(err) => store.dispatch(AppAction::ConsoleHighlight {
+                        line: format_output!("red", uuid, err),
+                        color: String::from("red"),
+                    }),
+                };
+            }
+            AppAction::StdinClose(ref uuid) => {
+                if let Err(err) = self.handler.close_stdin(uuid) {
+                    let _ = store.dispatch(AppAction::ConsoleHighlight {
+                        line: format_output!("red", uuid, err),
+                        color: String::from("red"),
+                    });
+                }
+            }
+            AppAction::CommandEnd { ref uuid, .. } => {
+                // nothing left to type into
+                if store.get_state().focused_cmd.as_ref() == Some(uuid) {
+                    let data = serde_json::from_str(NORMALMODE).expect("JSON Error!");
+                    let _ = store.dispatch(AppAction::SetMode(data));
+                }
+            }
             _ => {}
         }
         next(store, action)
     }
 }
 
-// self.tx.send(Event::CommandRun {
-//     func: self.handler.cmd_reg[command.clone()],
-//     uuid: uuid.to_string()
-// }).unwrap();
+impl CommandMiddleWare {
+    fn env<'a>(&'a self, state: &'a AppState) -> CommandEnv<'a> {
+        CommandEnv {
+            state,
+            tx: &self.tx,
+            handler: &self.handler,
+            scheduler: &self.scheduler,
+            aliases: &self.aliases,
+            registry: &self.registry,
+        }
+    }
+
+    fn run(
+        &self,
+        state: &AppState,
+        uuid: &str,
+        command: &str,
+        tokens: &[Token],
+    ) -> Result<Vec<AppAction>, String> {
+        let cmd_str = match tokens.first() {
+            Some(Token::Word(cmd_str)) => cmd_str.as_str(),
+            Some(op) => return Err(format!("Unexpected {:?}", op.as_str())),
+            None => return Err(String::from("Empty command")),
+        };
+        debug!("CMD STR {:?}", cmd_str);
+        let steps = self
+            .aliases
+            .lock()
+            .expect("Aliases Lock Poisoned")
+            .expand_macro(cmd_str, &tokens[1..]);
+        if let Some(steps) = steps {
+            // each step runs as a command of its own, started in order
+            let steps = steps?;
+            let mut actions = vec![AppAction::ConsolePush(format_output!(
+                "green",
+                cmd_str,
+                format!("Macro, {} steps", steps.len())
+            ))];
+            for step in steps {
+                actions.push(AppAction::CommandEnqueue {
+                    uuid: Uuid::new_v4().to_string(),
+                    cmd_str: step,
+                });
+            }
+            return Ok(actions);
+        }
+        let spec = self
+            .registry
+            .get(cmd_str)
+            .ok_or_else(|| format!("Unknown command {:?}", cmd_str))?;
+        let call = CommandCall {
+            uuid,
+            line: command,
+            args: &tokens[1..],
+        };
+        spec.validate(&call)?;
+        spec.execute(&self.env(state), &call)
+    }
+}
@@ -1,6 +1,7 @@
 use actions::AppAction;
+use middlewares::keyboard::COMMANDMODE;
 use redux::{DispatchFunc, Middleware, Store};
-use structs::app::AppState;
+use structs::app::{AppState, ModeSpec};
 
 pub struct CommandBarMiddleWare {}
 
@@ -13,13 +14,30 @@ impl Middleware<AppState> for CommandBarMiddleWare {
     ) -> Result<AppState, String> {
         debug!("3 {:?}", &action);
         if let AppAction::SetMode(ref mode) = action {
-            let _action = match mode["category"].as_str() {
-                Some("normal") => AppAction::CommandBarSet(String::from("")),
-                Some("command") => AppAction::CommandBarSet(String::from(":")),
-                Some(&_) | None => panic!("Invalid Mode Category"),
+            // the mode reducer ignores unknown modes, staying in the current one
+            let _action = match ModeSpec::of(mode) {
+                Some(spec) => AppAction::CommandBarSet(spec.prompt.to_string()),
+                None => AppAction::ConsoleHighlight {
+                    line: format_output!("red", "Mode", format!("Unknown mode {}", mode)),
+                    color: String::from("red"),
+                },
             };
             let _ = store.dispatch(_action);
         }
+        if let AppAction::PaletteAccept = action {
+            let state = store.get_state();
+            let command = state
+                .palette
+                .as_ref()
+                .and_then(|palette| palette.selected_entry())
+                .and_then(|entry| entry.command.clone());
+            // arguments are left to type
+            if let Some(command) = command {
+                let data = serde_json::from_str(COMMANDMODE).expect("JSON Error!");
+                let _ = store.dispatch(AppAction::SetMode(data));
+                let _ = store.dispatch(AppAction::CommandBarSet(format!(":{} ", command)));
+            }
+        }
         next(store, action)
     }
 }
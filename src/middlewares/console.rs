@@ -1,8 +1,19 @@
 use actions::AppAction;
+use middlewares::keyboard::NORMALMODE;
 use redux::{DispatchFunc, Middleware, Store};
+use std::fs::File;
+use std::io::Write;
+use std::sync::{mpsc, Mutex};
+use structs::app::events::Event;
 use structs::app::AppState;
+use structs::ui::OutputBuffer;
 
-pub struct ConsoleMiddleWare {}
+pub struct ConsoleMiddleWare {
+    /// Reaches the render loop, which owns the terminal.
+    pub tx: mpsc::Sender<Event>,
+    /// Where lines past the scrollback go, see `Settings::spill`.
+    pub spill: Option<Mutex<File>>,
+}
 
 impl Middleware<AppState> for ConsoleMiddleWare {
     fn dispatch(
@@ -23,32 +34,121 @@ impl Middleware<AppState> for ConsoleMiddleWare {
                     }
                 }
             }
-            &AppAction::CommandCreate(ref uuid) | &AppAction::CommandInvalid(ref uuid) => {
-                let cmd_str = store.get_state().cmd_str_queue[uuid].clone();
+            &AppAction::CommandCreate(ref uuid) => {
+                let cmd_str = store
+                    .get_state()
+                    .cmd_str_queue
+                    .get(uuid)
+                    .cloned()
+                    .unwrap_or_default();
                 let prompt_in = format_output!("white", uuid, &cmd_str);
                 let _ = store.dispatch(AppAction::ConsolePush(prompt_in));
             }
+            &AppAction::CommandInvalid {
+                ref uuid,
+                ref reason,
+            } => {
+                let cmd_str = store
+                    .get_state()
+                    .cmd_str_queue
+                    .get(uuid)
+                    .cloned()
+                    .unwrap_or_default();
+                let prompt_in = format_output!("white", uuid, &cmd_str);
+                let _ = store.dispatch(AppAction::ConsolePush(prompt_in));
+                let _ = store.dispatch(AppAction::ConsoleHighlight {
+                    line: format_output!("red", "!!!", reason),
+                    color: String::from("red"),
+                });
+            }
             &AppAction::CommandEnd {
                 ref uuid,
                 ref success,
                 ref reason,
             } => {
+                // the output itself stays in the command's own buffer
+                let lines = store
+                    .get_state()
+                    .cmd_output
+                    .get(uuid)
+                    .map_or(0, |lines| lines.len());
+                let summary = format!("{} lines, :tab {}", lines, &uuid[..uuid.len().min(8)]);
                 let prompt_in = match success {
-                    true => format_output!("green", uuid, "Process Terminated"),
-                    false => format_output!("red", uuid, reason),
+                    true => {
+                        format_output!("green", uuid, format!("Process Terminated ({})", summary))
+                    }
+                    false => format_output!("red", uuid, format!("{} ({})", reason, summary)),
                 };
                 let _ = store.dispatch(AppAction::ConsolePush(prompt_in));
             }
+            &AppAction::SelectionYank => {
+                let state = store.get_state();
+                if let Some(text) = state.selected_text() {
+                    let (first, last) = state.selection.expect("selected text").range();
+                    let _ = store.dispatch(AppAction::Yank(text));
+                    let line =
+                        format_output!("white", "Yank", format!("{} lines", last + 1 - first));
+                    let _ = store.dispatch(AppAction::ConsolePush(line));
+                }
+                let data = serde_json::from_str(NORMALMODE).expect("JSON Error!");
+                let _ = store.dispatch(AppAction::SetMode(data));
+            }
+            &AppAction::Yank(ref text) => {
+                let _ = self.tx.send(Event::Clipboard(text.clone()));
+            }
+            &AppAction::ConsolePush(_) | &AppAction::ConsoleHighlight { .. } => {
+                self.spill_dropped(store, None);
+            }
+            &AppAction::CommandOutput { ref uuid, .. } => {
+                self.spill_dropped(store, Some(uuid.as_str()));
+            }
             _ => {}
         }
         next(store, action)
     }
 }
 
+impl ConsoleMiddleWare {
+    // Appends the lines the coming push drops to the spill file, command
+    // lines tagged with the command's short uuid.
+    fn spill_dropped(&self, store: &Store<AppState>, uuid: Option<&str>) {
+        let file = match self.spill {
+            Some(ref file) => file,
+            None => return,
+        };
+        let state = store.get_state();
+        let output = match uuid {
+            Some(uuid) => state.cmd_output.get(uuid).cloned().unwrap_or_default(),
+            None => state.console.clone(),
+        };
+        let excess = (output.len() + 1).saturating_sub(state.scrollback.max(1));
+        if excess == 0 {
+            return;
+        }
+        let tag = uuid.map_or(String::new(), |uuid| {
+            format!("[{}] ", &uuid[..uuid.len().min(8)])
+        });
+        let text: String = output
+            .range(0, excess)
+            .map(|line| OutputBuffer::text(line).trim_end_matches('\n'))
+            .map(|line| format!("{}{}\n", tag, line))
+            .collect();
+        let mut file = file.lock().expect("spill file lock");
+        if let Err(err) = file.write_all(text.as_bytes()) {
+            debug!("SPILL FAILED {:?}", err);
+        }
+    }
+}
+
 fn get_action_in_queue(uuid: String, _store: &Store<AppState>) -> Result<AppAction, String> {
     match _store.get_state().cmd_str_queue.contains_key(&uuid) {
         true => {
-            let cmd_str = _store.get_state().cmd_str_queue[&uuid].clone();
+            let cmd_str = _store
+                .get_state()
+                .cmd_str_queue
+                .get(&uuid)
+                .cloned()
+                .unwrap_or_default();
             let prompt_in = format_output!("green", ">>>", &cmd_str);
             Ok(AppAction::ConsolePush(prompt_in))
         }
@@ -0,0 +1,37 @@
+use actions::AppAction;
+use redux::{DispatchFunc, Middleware, Store};
+use std::path::PathBuf;
+use structs::app::{AppState, History};
+
+/// Appends each line entered in the command bar to the history file.
+pub struct HistoryMiddleWare {
+    pub path: PathBuf,
+}
+
+impl Middleware<AppState> for HistoryMiddleWare {
+    fn dispatch(
+        &self,
+        store: &Store<AppState>,
+        action: AppAction,
+        next: &DispatchFunc<AppState>,
+    ) -> Result<AppState, String> {
+        debug!("8 {:?}", &action);
+        if let AppAction::CommandBarEnqueueCmd(_) = action {
+            let state = store.get_state();
+            let line: String = state.json_store["command"]
+                .as_str()
+                .expect("command is not str")
+                .chars()
+                .skip(1)
+                .collect();
+            // same filtering as History::push
+            let repeated = state.history.entries.last() == Some(&line);
+            if !line.trim().is_empty() && !repeated {
+                if let Err(err) = History::append(&self.path, &line) {
+                    debug!("History not saved {:?}", err);
+                }
+            }
+        }
+        next(store, action)
+    }
+}
@@ -1,27 +1,402 @@
 use actions::AppAction;
 use redux::{DispatchFunc, Middleware, Store};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use structs::app::events;
 use structs::app::events::Key as SerializableKey;
-use structs::app::AppState;
+use structs::app::{keys_name, AppState, KeySettingItem, ModeCategory, ModeSpec};
+use structs::ui::{CursorMove, Pane, Scroll};
 use termion::event::Key;
 use utils::app::to_unserializable;
 use uuid::Uuid;
 
-pub struct KeyboardMiddleWare {}
-
-const NORMALMODE: &'static str = r#"
+pub const NORMALMODE: &'static str = r#"
 {
     "category": "normal",
     "symbol": "NORM"
 }
 "#;
 
-const COMMANDMODE: &'static str = r#"
+pub const COMMANDMODE: &'static str = r#"
 {
     "category": "command",
     "symbol": "CTRL"
 }
 "#;
 
+pub const INPUTMODE: &'static str = r#"
+{
+    "category": "input",
+    "symbol": "INPT"
+}
+"#;
+
+pub const VISUALMODE: &'static str = r#"
+{
+    "category": "visual",
+    "symbol": "VIS"
+}
+"#;
+
+pub const TICKETMODE: &'static str = r#"
+{
+    "category": "ticket",
+    "symbol": "TCKT"
+}
+"#;
+
+/// Keys handled by the modes themselves, listed by `:help` and the palette
+/// next to the keymap.
+pub const KEYBINDINGS: &'static [(&'static str, &'static str, &'static str)] = &[
+    ("command", "Enter", "Run the command"),
+    (
+        "command",
+        "Tab / Shift-Tab",
+        "Complete, cycle through candidates",
+    ),
+    ("input", "Enter", "Send the line to the focused command"),
+    ("input", "Ctrl-D", "Close the focused command's stdin"),
+    ("ticket", "Other keys", "Type into the selected field"),
+    ("normal", "Click", "Select a tab, or ticket a bid or ask"),
+    ("normal", "Wheel", "Scroll the pane under the pointer"),
+];
+
+/// Bindings used unless `settings.json` rebinds the key.
+pub fn default_keymap() -> Vec<KeySettingItem> {
+    let mode = |json: &str| AppAction::SetMode(serde_json::from_str(json).expect("JSON Error!"));
+    let mut keymap = vec![
+        KeySettingItem {
+            mode: None,
+            ..KeySettingItem::action(
+                "normal",
+                SerializableKey::Ctrl('p'),
+                AppAction::PaletteOpen,
+                "Open the command palette",
+            )
+        },
+        KeySettingItem::action(
+            "normal",
+            SerializableKey::Char(':'),
+            mode(COMMANDMODE),
+            "Enter command mode",
+        ),
+        KeySettingItem::action(
+            "normal",
+            SerializableKey::Char('g'),
+            AppAction::TabNext,
+            "Next tab",
+        )
+        .followed_by(&[SerializableKey::Char('t')]),
+        KeySettingItem::action(
+            "normal",
+            SerializableKey::Char('g'),
+            AppAction::TabPrevious,
+            "Previous tab",
+        )
+        .followed_by(&[SerializableKey::Char('T')]),
+        KeySettingItem::action(
+            "command",
+            SerializableKey::Up,
+            AppAction::HistoryPrev,
+            "Previous command in history",
+        ),
+        KeySettingItem::action(
+            "command",
+            SerializableKey::Down,
+            AppAction::HistoryNext,
+            "Next command in history",
+        ),
+        KeySettingItem::action(
+            "command",
+            SerializableKey::Ctrl('r'),
+            AppAction::HistorySearch(String::new()),
+            "Search history",
+        ),
+    ];
+    let editing = vec![
+        (
+            SerializableKey::Esc,
+            mode(NORMALMODE),
+            "Back to normal mode",
+        ),
+        (
+            SerializableKey::Backspace,
+            AppAction::CommandBarPop(1),
+            "Delete before the cursor",
+        ),
+        (
+            SerializableKey::Delete,
+            AppAction::CommandBarDelete(1),
+            "Delete under the cursor",
+        ),
+        (
+            SerializableKey::Left,
+            AppAction::CommandBarMove(CursorMove::Left),
+            "Cursor left",
+        ),
+        (
+            SerializableKey::Right,
+            AppAction::CommandBarMove(CursorMove::Right),
+            "Cursor right",
+        ),
+        (
+            SerializableKey::Home,
+            AppAction::CommandBarMove(CursorMove::Home),
+            "Start of line",
+        ),
+        (
+            SerializableKey::Ctrl('a'),
+            AppAction::CommandBarMove(CursorMove::Home),
+            "Start of line",
+        ),
+        (
+            SerializableKey::End,
+            AppAction::CommandBarMove(CursorMove::End),
+            "End of line",
+        ),
+        (
+            SerializableKey::Ctrl('e'),
+            AppAction::CommandBarMove(CursorMove::End),
+            "End of line",
+        ),
+        (
+            SerializableKey::Alt('b'),
+            AppAction::CommandBarMove(CursorMove::WordLeft),
+            "Word left",
+        ),
+        (
+            SerializableKey::Alt('f'),
+            AppAction::CommandBarMove(CursorMove::WordRight),
+            "Word right",
+        ),
+        (
+            SerializableKey::Ctrl('w'),
+            AppAction::CommandBarKill(CursorMove::WordLeft),
+            "Cut word",
+        ),
+        (
+            SerializableKey::Ctrl('u'),
+            AppAction::CommandBarKill(CursorMove::Home),
+            "Cut to start",
+        ),
+        (
+            SerializableKey::Ctrl('k'),
+            AppAction::CommandBarKill(CursorMove::End),
+            "Cut to end",
+        ),
+        (
+            SerializableKey::Ctrl('y'),
+            AppAction::CommandBarYank,
+            "Paste what was cut",
+        ),
+    ];
+    // the command bar is edited the same way in both modes
+    for category in &["command", "input"] {
+        for (key, action, description) in &editing {
+            keymap.push(KeySettingItem::action(
+                category,
+                *key,
+                action.clone(),
+                description,
+            ));
+        }
+    }
+    let navigation = vec![
+        (
+            vec![SerializableKey::Char('j')],
+            Scroll::LineDown,
+            "Scroll down",
+        ),
+        (vec![SerializableKey::Down], Scroll::LineDown, "Scroll down"),
+        (
+            vec![SerializableKey::Char('k')],
+            Scroll::LineUp,
+            "Scroll up",
+        ),
+        (vec![SerializableKey::Up], Scroll::LineUp, "Scroll up"),
+        (
+            vec![SerializableKey::PageDown],
+            Scroll::PageDown,
+            "Page down",
+        ),
+        (
+            vec![SerializableKey::Ctrl('f')],
+            Scroll::PageDown,
+            "Page down",
+        ),
+        (vec![SerializableKey::PageUp], Scroll::PageUp, "Page up"),
+        (vec![SerializableKey::Ctrl('b')], Scroll::PageUp, "Page up"),
+        (
+            vec![SerializableKey::Char('g'), SerializableKey::Char('g')],
+            Scroll::Top,
+            "Scroll to the top",
+        ),
+        (
+            vec![SerializableKey::Char('G')],
+            Scroll::Bottom,
+            "Scroll to the end, following new lines",
+        ),
+    ];
+    // visual mode moves the end of the selection the same way
+    for (keys, scroll, description) in navigation {
+        keymap.push(
+            KeySettingItem::action("normal", keys[0], AppAction::Scroll(scroll), description)
+                .followed_by(&keys[1..]),
+        );
+        keymap.push(
+            KeySettingItem::action(
+                "visual",
+                keys[0],
+                AppAction::SelectionMove(scroll),
+                description,
+            )
+            .followed_by(&keys[1..]),
+        );
+    }
+    let window = SerializableKey::Ctrl('w');
+    let focus = vec![
+        (
+            SerializableKey::Char('w'),
+            AppAction::PaneFocusNext,
+            "Focus the other pane",
+        ),
+        (
+            SerializableKey::Char('h'),
+            AppAction::PaneFocus(Pane::Output),
+            "Focus the output",
+        ),
+        (
+            SerializableKey::Char('l'),
+            AppAction::PaneFocus(Pane::Markets),
+            "Focus the markets",
+        ),
+    ];
+    for (key, action, description) in focus {
+        keymap.push(
+            KeySettingItem::action("normal", window, action, description).followed_by(&[key]),
+        );
+    }
+    keymap.extend(vec![
+        KeySettingItem::action(
+            "normal",
+            SerializableKey::Char('v'),
+            mode(VISUALMODE),
+            "Select lines to copy",
+        ),
+        KeySettingItem::action(
+            "visual",
+            SerializableKey::Char('y'),
+            AppAction::SelectionYank,
+            "Copy the selected lines",
+        ),
+        KeySettingItem::action(
+            "visual",
+            SerializableKey::Esc,
+            mode(NORMALMODE),
+            "Back to normal mode",
+        ),
+        KeySettingItem::action(
+            "visual",
+            SerializableKey::Char('v'),
+            mode(NORMALMODE),
+            "Back to normal mode",
+        ),
+        KeySettingItem {
+            leader: true,
+            ..KeySettingItem::command(
+                "normal",
+                SerializableKey::Char('b'),
+                "ticket buy",
+                "Buy the selected market",
+            )
+        },
+        KeySettingItem {
+            leader: true,
+            ..KeySettingItem::command(
+                "normal",
+                SerializableKey::Char('s'),
+                "ticket sell",
+                "Sell the selected market",
+            )
+        },
+    ]);
+    let ticket = vec![
+        (
+            SerializableKey::Char('\t'),
+            AppAction::TicketSelect(1),
+            "Next field",
+        ),
+        (
+            SerializableKey::Down,
+            AppAction::TicketSelect(1),
+            "Next field",
+        ),
+        (
+            SerializableKey::BackTab,
+            AppAction::TicketSelect(-1),
+            "Previous field",
+        ),
+        (
+            SerializableKey::Up,
+            AppAction::TicketSelect(-1),
+            "Previous field",
+        ),
+        (
+            SerializableKey::Backspace,
+            AppAction::TicketErase,
+            "Delete a char",
+        ),
+        (
+            SerializableKey::Ctrl('t'),
+            AppAction::TicketSide,
+            "Switch between buy and sell",
+        ),
+        (
+            SerializableKey::Char('\n'),
+            AppAction::TicketSubmit,
+            "Place the order",
+        ),
+        (SerializableKey::Esc, mode(NORMALMODE), "Cancel the order"),
+    ];
+    for (key, action, description) in ticket {
+        keymap.push(KeySettingItem::action("ticket", key, action, description));
+    }
+    keymap
+}
+
+/// Mode, keys and description of every binding in effect, for `:help` and
+/// the palette. Entries shadowed by an earlier one for the same keys are
+/// left out.
+pub fn key_help(keymap: &[KeySettingItem]) -> Vec<(String, String, String)> {
+    let mut help: Vec<(String, String, String)> = Vec::new();
+    for (index, item) in keymap.iter().enumerate() {
+        let shadowed = keymap[..index]
+            .iter()
+            .any(|earlier| earlier.keys() == item.keys() && earlier.mode == item.mode);
+        if !shadowed {
+            let mode = item.mode.clone().unwrap_or_else(|| String::from("any"));
+            help.push((mode, keys_name(&item.keys()), item.describe()));
+        }
+    }
+    help.extend(KEYBINDINGS.iter().map(|(mode, keys, description)| {
+        (mode.to_string(), keys.to_string(), description.to_string())
+    }));
+    help
+}
+
+// Highest count prefix honored, `99999j` scrolls as much as this.
+const MAX_COUNT: usize = 999;
+
+pub struct KeyboardMiddleWare {
+    pub tx: mpsc::Sender<events::Event>,
+    /// How long a partial sequence waits for its next key.
+    pub timeout: Duration,
+    // bumped on every key, so only the timer of the last one fires
+    pub generation: AtomicUsize,
+}
+
 impl Middleware<AppState> for KeyboardMiddleWare {
     fn dispatch(
         &self,
@@ -30,49 +405,296 @@ impl Middleware<AppState> for KeyboardMiddleWare {
         next: &DispatchFunc<AppState>,
     ) -> Result<AppState, String> {
         debug!("2 {:?}", &action);
-        if let AppAction::Keyboard(key) = action {
-            let _state = store.get_state();
-            match get_key_action(key, _state) {
-                Ok(_action) => {
-                    let _ = store.dispatch(_action);
+        match action {
+            AppAction::Keyboard(key) => {
+                let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+                let mut keys = store.get_state().pending_keys;
+                keys.push(key);
+                if self.resolve(store, keys, true) {
+                    let tx = self.tx.clone();
+                    let timeout = self.timeout;
+                    thread::spawn(move || {
+                        thread::sleep(timeout);
+                        let _ = tx.send(AppAction::KeyTimeout(generation).into_event());
+                    });
                 }
-                Err(err) => debug!("[ERR] {:?}", err),
             }
-        };
+            AppAction::KeyTimeout(generation) => {
+                let keys = store.get_state().pending_keys;
+                if generation == self.generation.load(Ordering::SeqCst) && !keys.is_empty() {
+                    self.resolve(store, keys, false);
+                }
+            }
+            _ => {}
+        }
         next(store, action)
     }
 }
 
+impl KeyboardMiddleWare {
+    // Runs the binding of `keys`, or keeps them pending when they start a
+    // longer one and `wait` is set. Keys that end up bound to nothing are
+    // handled one by one. Returns whether the keys are pending.
+    fn resolve(&self, store: &Store<AppState>, keys: Vec<SerializableKey>, wait: bool) -> bool {
+        let state = store.get_state();
+        // overlays take keys as they come
+        let overlay =
+            state.palette.is_some() || state.completion.is_some() || state.history.search.is_some();
+        let category = state.json_store["mode"]["category"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        let (count, rest) = split_count(&category, &keys);
+        let partial = rest.is_empty()
+            || state
+                .keymap
+                .iter()
+                .any(|item| item.extends(&category, rest));
+        if wait && partial && !overlay {
+            let _ = store.dispatch(AppAction::KeyPending(keys));
+            return true;
+        }
+        if !state.pending_keys.is_empty() {
+            let _ = store.dispatch(AppAction::KeyPending(Vec::new()));
+        }
+        let bound = match overlay {
+            true => None,
+            false => state
+                .keymap
+                .iter()
+                .find(|item| item.applies(&category, rest)),
+        };
+        match bound {
+            Some(item) => {
+                for _ in 0..count {
+                    let _ = store.dispatch(bound_action(item));
+                }
+            }
+            None => {
+                for key in keys {
+                    match get_key_action(key, store.get_state()) {
+                        Ok(_action) => {
+                            let _ = store.dispatch(_action);
+                        }
+                        Err(err) => debug!("[ERR] {:?}", err),
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+// A count typed before a sequence in modes taking them, `5` in `5j`, and the
+// keys after it.
+fn split_count<'a>(category: &str, keys: &'a [SerializableKey]) -> (usize, &'a [SerializableKey]) {
+    if !ModeSpec::get(category).map_or(false, |spec| spec.counts) {
+        return (1, keys);
+    }
+    let digits: String = keys
+        .iter()
+        .map(|key| match key {
+            SerializableKey::Char(c) => *c,
+            _ => ' ',
+        })
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    // a leading 0 isn't a count
+    match digits.starts_with('0') {
+        true => (1, keys),
+        false => {
+            let count = digits
+                .parse::<usize>()
+                .map_or(1, |count| count.min(MAX_COUNT));
+            (count, &keys[digits.len()..])
+        }
+    }
+}
+
+fn bound_action(item: &KeySettingItem) -> AppAction {
+    match item.command {
+        Some(ref command) => AppAction::CommandEnqueue {
+            uuid: Uuid::new_v4().to_string(),
+            cmd_str: command.clone(),
+        },
+        None => item.action.clone().expect("validated binding"),
+    }
+}
+
 fn get_key_action(_key: SerializableKey, _state: AppState) -> Result<AppAction, String> {
     let key_event = to_unserializable(_key);
-    match _state.json_store["mode"]["category"].as_str() {
-        Some("normal") => normal_key(key_event, _state),
-        Some("command") => command_key(key_event, _state),
-        Some(&_) | None => panic!("Unknown Category !"),
+    if let Some(ref palette) = _state.palette {
+        return palette_key(key_event, palette.query.clone());
+    }
+    let category = _state.json_store["mode"]["category"]
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    let bound = _state
+        .keymap
+        .iter()
+        .find(|item| item.applies(&category, &[_key]))
+        .map(bound_action);
+    match ModeSpec::get(&category).map(|spec| spec.category) {
+        Some(ModeCategory::Normal) | Some(ModeCategory::Visual) => {
+            bound.ok_or_else(|| String::from("There is no settings for this key yet"))
+        }
+        Some(ModeCategory::Command) => command_key(key_event, _state, bound),
+        Some(ModeCategory::Input) => input_key(key_event, _state, bound),
+        Some(ModeCategory::Ticket) => ticket_key(key_event, bound),
+        // however the state got there, Esc gets out of it
+        None => match key_event {
+            Key::Esc => Ok(AppAction::SetMode(
+                serde_json::from_str(NORMALMODE).expect("JSON Error!"),
+            )),
+            _ => Err(format!("Unknown mode {:?}", category)),
+        },
     }
 }
 
-fn normal_key(_key: Key, _state: AppState) -> Result<AppAction, String> {
+// Printable keys nothing is bound to go into the selected field.
+fn ticket_key(_key: Key, bound: Option<AppAction>) -> Result<AppAction, String> {
+    if let Some(action) = bound {
+        return Ok(action);
+    }
     match _key {
-        Key::Char(':') => {
-            let data = serde_json::from_str(COMMANDMODE).expect("JSON Error!");
-            let action = AppAction::SetMode(data);
-            Ok(action)
+        Key::Char(_char) if !_char.is_control() => Ok(AppAction::TicketInput(_char)),
+        _ => Err(String::from("Key not Implemented")),
+    }
+}
+
+fn command_key(
+    _key: Key,
+    mut _state: AppState,
+    bound: Option<AppAction>,
+) -> Result<AppAction, String> {
+    if let Some(query) = _state.history.search.clone() {
+        return search_key(_key, query);
+    }
+    if _state.completion.is_some() {
+        if let Some(action) = completion_key(&_key) {
+            return Ok(action);
         }
-        _ => Err(String::from("There is no settings for this key yet")),
+    }
+    if let Some(action) = bound {
+        return Ok(action);
+    }
+    let at_end = _state.json_store["command"]
+        .as_str()
+        .map_or(true, |line| _state.cmd_cursor >= line.chars().count());
+    match _key {
+        Key::Char('\t') if at_end => Ok(AppAction::CommandBarComplete),
+        Key::Char('\t') => Err(String::from("Completion needs the cursor at the end")),
+        Key::Char('\n') => Ok(AppAction::CommandBarEnqueueCmd(Uuid::new_v4().to_string())),
+        Key::Char(_char) => Ok(AppAction::CommandBarPush(_char)),
+        _ => Err(String::from("Key not Implemented")),
     }
 }
 
-fn command_key(_key: Key, mut _state: AppState) -> Result<AppAction, String> {
+// The palette takes every key while open, typing filters it.
+fn palette_key(_key: Key, mut query: String) -> Result<AppAction, String> {
     match _key {
-        Key::Esc => {
-            let data = serde_json::from_str(NORMALMODE).expect("JSON Error!");
-            let action = AppAction::SetMode(data);
-            Ok(action)
+        Key::Esc | Key::Ctrl('p') => Ok(AppAction::PaletteSet(None)),
+        Key::Char('\n') => Ok(AppAction::PaletteAccept),
+        Key::Up | Key::BackTab => Ok(AppAction::PaletteSelect(-1)),
+        Key::Down | Key::Char('\t') => Ok(AppAction::PaletteSelect(1)),
+        Key::Backspace => {
+            query.pop();
+            Ok(AppAction::PaletteQuery(query))
+        }
+        Key::Char(_char) => {
+            query.push(_char);
+            Ok(AppAction::PaletteQuery(query))
+        }
+        _ => Err(String::from("Key not Implemented")),
+    }
+}
+
+// While the popup is open Tab and the arrows cycle through its candidates,
+// other keys close it through the command bar reducers.
+fn completion_key(_key: &Key) -> Option<AppAction> {
+    match _key {
+        Key::Char('\t') | Key::Down => Some(AppAction::CompletionSelect(1)),
+        Key::BackTab | Key::Up => Some(AppAction::CompletionSelect(-1)),
+        Key::Esc | Key::Char('\n') => Some(AppAction::CompletionSet(None)),
+        _ => None,
+    }
+}
+
+// Ctrl-R reverse search, Enter keeps the match in the bar for editing.
+fn search_key(_key: Key, mut query: String) -> Result<AppAction, String> {
+    match _key {
+        Key::Esc | Key::Ctrl('g') => Ok(AppAction::HistorySearchEnd(false)),
+        Key::Char('\n') => Ok(AppAction::HistorySearchEnd(true)),
+        Key::Ctrl('r') => Ok(AppAction::HistorySearchNext),
+        Key::Backspace => {
+            query.pop();
+            Ok(AppAction::HistorySearch(query))
+        }
+        Key::Char(_char) => {
+            query.push(_char);
+            Ok(AppAction::HistorySearch(query))
+        }
+        _ => Err(String::from("Key not Implemented")),
+    }
+}
+
+// Lines typed here go to the stdin of the focused command.
+fn input_key(_key: Key, _state: AppState, bound: Option<AppAction>) -> Result<AppAction, String> {
+    if let Some(action) = bound {
+        return Ok(action);
+    }
+    let focused = _state
+        .focused_cmd
+        .clone()
+        .ok_or_else(|| String::from("No focused command"));
+    match _key {
+        Key::Ctrl('d') => Ok(AppAction::StdinClose(focused?)),
+        Key::Char('\n') => {
+            let value = _state.json_store["command"]
+                .as_str()
+                .expect("command is not str");
+            Ok(AppAction::StdinSend {
+                uuid: focused?,
+                line: value.chars().skip(1).collect(),
+            })
         }
-        Key::Backspace => Ok(AppAction::CommandBarPop(1)),
-        Key::Char('\n') => Ok(AppAction::CommandBarEnqueueCmd(Uuid::new_v4().to_string())),
         Key::Char(_char) => Ok(AppAction::CommandBarPush(_char)),
         _ => Err(String::from("Key not Implemented")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<SerializableKey> {
+        s.chars().map(SerializableKey::Char).collect()
+    }
+
+    #[test]
+    fn splits_counts() {
+        let keys = chars("12gt");
+        assert_eq!(split_count("normal", &keys), (12, &keys[2..]));
+        let keys = chars("gt");
+        assert_eq!(split_count("normal", &keys), (1, &keys[..]));
+        let keys = chars("5");
+        assert_eq!(split_count("visual", &keys), (5, &keys[1..]));
+    }
+
+    #[test]
+    fn counts_only_where_the_mode_takes_them() {
+        let keys = chars("5j");
+        assert_eq!(split_count("command", &keys), (1, &keys[..]));
+        assert_eq!(split_count("nowhere", &keys), (1, &keys[..]));
+        // a leading 0 is a key of its own
+        let keys = chars("05j");
+        assert_eq!(split_count("normal", &keys), (1, &keys[..]));
+    }
+
+    #[test]
+    fn caps_counts() {
+        let keys = chars("123456j");
+        assert_eq!(split_count("normal", &keys), (MAX_COUNT, &keys[6..]));
+    }
+}
@@ -1,10 +1,18 @@
+pub mod alert;
 pub mod command;
 pub mod command_bar;
 pub mod console;
 pub mod debug;
+pub mod history;
 pub mod keyboard;
+pub mod mouse;
+pub mod strategy;
+pub use self::alert::AlertMiddleWare;
 pub use self::command::CommandMiddleWare;
 pub use self::command_bar::CommandBarMiddleWare;
 pub use self::console::ConsoleMiddleWare;
 pub use self::debug::DebugMiddleWare;
+pub use self::history::HistoryMiddleWare;
 pub use self::keyboard::KeyboardMiddleWare;
+pub use self::mouse::MouseMiddleWare;
+pub use self::strategy::StrategyMiddleWare;
@@ -0,0 +1,91 @@
+use actions::AppAction;
+use components::{app, markets};
+use redux::{DispatchFunc, Middleware, Store};
+use structs::app::events::{Mouse, MouseButton};
+use structs::app::{get_quotes, AppState, ModeCategory, ModeSpec};
+use structs::ui::{Pane, Scroll};
+use tui::layout::Rect;
+use uuid::Uuid;
+
+// Lines scrolled by a turn of the wheel.
+const WHEEL_LINES: usize = 3;
+
+/// Turns clicks and wheel turns into actions, hit-testing them against the
+/// layout `components::app` renders.
+pub struct MouseMiddleWare {}
+
+impl Middleware<AppState> for MouseMiddleWare {
+    fn dispatch(
+        &self,
+        store: &Store<AppState>,
+        action: AppAction,
+        next: &DispatchFunc<AppState>,
+    ) -> Result<AppState, String> {
+        debug!("9 {:?}", &action);
+        if let AppAction::Mouse(Mouse::Press(button, x, y)) = action {
+            let state = store.get_state();
+            // the palette takes the whole screen's attention
+            if state.palette.is_none() {
+                // termion counts from 1, tui from 0
+                let (x, y) = (x.saturating_sub(1), y.saturating_sub(1));
+                for action in mouse_actions(&state, button, x, y) {
+                    let _ = store.dispatch(action);
+                }
+            }
+        }
+        next(store, action)
+    }
+}
+
+fn mouse_actions(state: &AppState, button: MouseButton, x: u16, y: u16) -> Vec<AppAction> {
+    let (cols, rows) = state.size;
+    let areas = app::areas(Rect::new(0, 0, cols, rows), state);
+    let category = ModeSpec::of(&state.json_store["mode"]).map(|spec| spec.category);
+    let within = |area: Rect| x >= area.x && x < area.right() && y >= area.y && y < area.bottom();
+    let pane = match areas.markets {
+        Some(area) if within(area) => Some(Pane::Markets),
+        _ if within(areas.output) => Some(Pane::Output),
+        _ => None,
+    };
+    match button {
+        MouseButton::WheelUp | MouseButton::WheelDown => {
+            let scroll = match button {
+                MouseButton::WheelUp => Scroll::LineUp,
+                _ => Scroll::LineDown,
+            };
+            // the wheel scrolls the pane under the pointer
+            let mut actions = match pane {
+                Some(pane) => vec![AppAction::PaneFocus(pane)],
+                None => return vec![],
+            };
+            actions.extend((0..WHEEL_LINES).map(|_| AppAction::Scroll(scroll)));
+            actions
+        }
+        // clicks would move the output from under a visual selection
+        _ if category == Some(ModeCategory::Visual) => vec![],
+        MouseButton::Left if within(areas.tabs) => app::tabs(state)
+            .title_at(x - areas.tabs.x)
+            .map_or(vec![], |index| vec![AppAction::TabSelect(index)]),
+        MouseButton::Left if pane == Some(Pane::Markets) => {
+            let area = areas.markets.expect("markets pane");
+            let (row, side) = match markets::row_at(state, area, x, y) {
+                Some(hit) => hit,
+                None => return vec![AppAction::PaneFocus(Pane::Markets)],
+            };
+            let mut actions = vec![AppAction::MarketSelect(row)];
+            // a click on a bid or an ask prefills a ticket taking it
+            let ticketing =
+                category == Some(ModeCategory::Normal) || category == Some(ModeCategory::Ticket);
+            if let (Some(side), true) = (side, ticketing) {
+                let symbol = get_quotes(state)[row].symbol.clone();
+                actions.push(AppAction::CommandEnqueue {
+                    uuid: Uuid::new_v4().to_string(),
+                    cmd_str: format!("ticket {} {}", side.name(), symbol),
+                });
+            }
+            actions
+        }
+        MouseButton::Left if pane == Some(Pane::Output) => vec![AppAction::PaneFocus(Pane::Output)],
+        _ => vec![],
+    }
+}
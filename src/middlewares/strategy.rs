@@ -0,0 +1,96 @@
+use actions::AppAction;
+use redux::{DispatchFunc, Middleware, Store};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+use strategies::StrategyRegistry;
+use structs::app::events::Event;
+use structs::app::{AppState, Quote, RiskLimits};
+use uuid::Uuid;
+
+/// Hands quotes to the strategies, which run on a thread of their own so a
+/// slow one holds up neither the dispatch nor the render.
+pub struct StrategyMiddleWare {
+    // None when no strategy is registered
+    quotes: Option<mpsc::Sender<Quote>>,
+}
+
+impl StrategyMiddleWare {
+    pub fn new(
+        registry: StrategyRegistry,
+        risk: RiskLimits,
+        tx: mpsc::Sender<Event>,
+    ) -> StrategyMiddleWare {
+        if registry.is_empty() {
+            return StrategyMiddleWare { quotes: None };
+        }
+        let (quote_tx, quote_rx) = mpsc::channel();
+        thread::spawn(move || run(registry, risk, quote_rx, tx));
+        StrategyMiddleWare {
+            quotes: Some(quote_tx),
+        }
+    }
+}
+
+impl Middleware<AppState> for StrategyMiddleWare {
+    fn dispatch(
+        &self,
+        store: &Store<AppState>,
+        action: AppAction,
+        next: &DispatchFunc<AppState>,
+    ) -> Result<AppState, String> {
+        debug!("6 {:?}", &action);
+        if let (AppAction::QuoteUpdate(ref quote), Some(ref quotes)) = (&action, &self.quotes) {
+            let _ = quotes.send(quote.clone());
+        }
+        next(store, action)
+    }
+}
+
+fn run(
+    mut registry: StrategyRegistry,
+    risk: RiskLimits,
+    rx: mpsc::Receiver<Quote>,
+    tx: mpsc::Sender<Event>,
+) {
+    let mut latest: HashMap<String, Quote> = HashMap::new();
+    // last reported signal per strategy, only changes reach the console
+    let mut signals: HashMap<String, i64> = HashMap::new();
+    while let Ok(quote) = rx.recv() {
+        latest.insert(quote.symbol.clone(), quote);
+        // strategies slower than the feed get the latest quotes, not every tick
+        while let Ok(quote) = rx.try_recv() {
+            latest.insert(quote.symbol.clone(), quote);
+        }
+        let mut quotes: Vec<Quote> = latest.values().cloned().collect();
+        quotes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        for (name, result) in registry.run(&quotes) {
+            let (signal, orders) = match result {
+                Ok(result) => result,
+                Err(err) => {
+                    let line = format_output!("red", name, err);
+                    let _ = tx.send(AppAction::ConsolePush(line).into_event());
+                    continue;
+                }
+            };
+            if signals.insert(name.clone(), signal) != Some(signal) {
+                let line = format_output!("green", name, format!("Signal {}", signal));
+                let _ = tx.send(AppAction::ConsolePush(line).into_event());
+            }
+            for order in orders {
+                let evt = match risk.check(&order) {
+                    Ok(()) => AppAction::CommandEnqueue {
+                        uuid: Uuid::new_v4().to_string(),
+                        cmd_str: order.command(),
+                    },
+                    Err(err) => AppAction::ConsolePush(format_output!(
+                        "red",
+                        name,
+                        format!("{}: {}", order.command(), err)
+                    )),
+                };
+                let _ = tx.send(evt.into_event());
+            }
+        }
+    }
+}
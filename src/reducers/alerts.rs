@@ -0,0 +1,74 @@
+use actions::AppAction;
+use reducers::ReducerFn;
+use std::sync::Arc;
+use structs::app::AppState;
+
+pub fn create() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::AlertCreate(alert) => {
+                    Arc::make_mut(&mut state.alerts).push(alert.clone());
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn trigger() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::AlertTrigger(id) => {
+                    if let Some(alert) = Arc::make_mut(&mut state.alerts)
+                        .iter_mut()
+                        .find(|a| a.id == *id)
+                    {
+                        alert.triggered = true;
+                        state.alert_flash = Some((*id, alert.describe()));
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn remove() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::AlertRemove(id) => {
+                    Arc::make_mut(&mut state.alerts).retain(|a| a.id != *id);
+                    if state.alert_flash.as_ref().map(|flash| flash.0) == Some(*id) {
+                        state.alert_flash = None;
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn flash_clear() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::AlertFlashClear(id) => {
+                    // a newer alert may have taken over the segment meanwhile
+                    if let Some((flash_id, _)) = state.alert_flash {
+                        if flash_id == *id {
+                            state.alert_flash = None;
+                        }
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
@@ -1,14 +1,58 @@
 use actions::AppAction;
 use reducers::ReducerFn;
 use serde_json::Value;
+use std::sync::Arc;
 use structs::app::AppState;
+use structs::ui::byte_index;
+
+fn line(state: &AppState) -> String {
+    state.json_store["command"]
+        .as_str()
+        .expect("command is not str")
+        .to_string()
+}
+
+// The `:` or `>` of command and input mode can't be edited.
+fn prefix_len(line: &str) -> usize {
+    match line.chars().next() {
+        Some(':') | Some('>') => 1,
+        _ => 0,
+    }
+}
+
+/// Replaces the whole line, with the cursor at its end.
+pub fn set_line(state: &mut AppState, line: String) {
+    state.cmd_cursor = line.chars().count();
+    Arc::make_mut(&mut state.json_store)["command"] = Value::String(line);
+}
+
+// Removes the chars between two cursor positions, returning them.
+fn remove_range(state: &mut AppState, from: usize, to: usize) -> String {
+    let mut value = line(state);
+    let (from, to) = (from.min(to), from.max(to));
+    let removed: String = value
+        .drain(byte_index(&value, from)..byte_index(&value, to))
+        .collect();
+    Arc::make_mut(&mut state.json_store)["command"] = Value::String(value);
+    state.cmd_cursor = from;
+    removed
+}
+
+fn insert(state: &mut AppState, text: &str) {
+    let mut value = line(state);
+    let cursor = state.cmd_cursor.max(prefix_len(&value));
+    value.insert_str(byte_index(&value, cursor), text);
+    Arc::make_mut(&mut state.json_store)["command"] = Value::String(value);
+    state.cmd_cursor = cursor + text.chars().count();
+}
 
 pub fn set() -> Box<ReducerFn> {
     Box::new(
         |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
                 AppAction::CommandBarSet(str_ref) => {
-                    state.json_store["command"] = Value::String(str_ref.to_string());
+                    state.completion = None;
+                    set_line(&mut state, str_ref.to_string());
                     Ok(state)
                 }
                 _ => Ok(state),
@@ -22,14 +66,8 @@ pub fn push() -> Box<ReducerFn> {
         |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
                 AppAction::CommandBarPush(_char) => {
-                    let value = state.json_store["command"]
-                        .as_str()
-                        .expect("command is not str");
-                    let mut process_value = value.to_string();
-
-                    process_value.push(*_char);
-
-                    state.json_store["command"] = Value::String(process_value);
+                    state.completion = None;
+                    insert(&mut state, &_char.to_string());
                     Ok(state)
                 }
                 _ => Ok(state),
@@ -38,19 +76,74 @@ pub fn push() -> Box<ReducerFn> {
     )
 }
 
+/// Deletes up to n chars before the cursor.
 pub fn pop() -> Box<ReducerFn> {
     Box::new(
         |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
                 AppAction::CommandBarPop(_pop_index) => {
-                    let value = state.json_store["command"]
-                        .as_str()
-                        .expect("command is not str");
-                    let mut process_value = value.to_string();
-
-                    if process_value.len() > 1 {
-                        process_value.pop();
-                        state.json_store["command"] = Value::String(process_value);
+                    state.completion = None;
+                    let start = prefix_len(&line(&state));
+                    let cursor = state.cmd_cursor.max(start);
+                    let from = cursor.saturating_sub(*_pop_index as usize).max(start);
+                    remove_range(&mut state, from, cursor);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+/// Deletes up to n chars from the cursor on.
+pub fn delete() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandBarDelete(count) => {
+                    state.completion = None;
+                    let value = line(&state);
+                    let cursor = state.cmd_cursor.max(prefix_len(&value));
+                    let to = (cursor + *count as usize).min(value.chars().count());
+                    remove_range(&mut state, cursor, to);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn move_cursor() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandBarMove(movement) => {
+                    let value = line(&state);
+                    state.cmd_cursor =
+                        movement.target(&value, state.cmd_cursor, prefix_len(&value));
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+/// Cuts the text between the cursor and where the movement would take it.
+pub fn kill() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandBarKill(movement) => {
+                    state.completion = None;
+                    let value = line(&state);
+                    let start = prefix_len(&value);
+                    let cursor = state.cmd_cursor.max(start);
+                    let target = movement.target(&value, cursor, start);
+                    let killed = remove_range(&mut state, cursor, target);
+                    if !killed.is_empty() {
+                        state.kill_buffer = killed;
                     }
                     Ok(state)
                 }
@@ -60,21 +153,64 @@ pub fn pop() -> Box<ReducerFn> {
     )
 }
 
+pub fn yank() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandBarYank => {
+                    state.completion = None;
+                    let killed = state.kill_buffer.clone();
+                    insert(&mut state, &killed);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn copy() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::Yank(text) => {
+                    state.kill_buffer = text.clone();
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
 pub fn enqueue_cmd() -> Box<ReducerFn> {
     Box::new(
         |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
                 AppAction::CommandBarEnqueueCmd(uuid) => {
-                    let value = state.json_store["command"]
-                        .as_str()
-                        .expect("command is not str");
-                    let mut process_value = value.to_string();
+                    state.completion = None;
+                    let mut process_value = line(&state);
+                    let cmd_str = process_value.split_off(1);
 
-                    state
-                        .cmd_str_queue
-                        .insert(uuid.clone(), process_value.split_off(1));
+                    state.history.push(&cmd_str);
+                    Arc::make_mut(&mut state.cmd_str_queue).insert(uuid.clone(), cmd_str);
 
-                    state.json_store["command"] = Value::String(process_value);
+                    set_line(&mut state, process_value);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+// Input mode keeps a `>` prompt in the bar, the line sent to stdin follows it.
+pub fn stdin_sent() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::StdinSend { .. } => {
+                    set_line(&mut state, String::from(">"));
                     Ok(state)
                 }
                 _ => Ok(state),
@@ -1,9 +1,11 @@
 use actions::AppAction;
 use reducers::ReducerFn;
+use serde_json::{json, Value};
+use std::sync::Arc;
 use structs::app::{AppState, Command};
 
-fn get_index_by_uuid(arr: &[Command], uuid: &str) -> usize {
-    arr.iter().position(|ref r| r.id == uuid).unwrap()
+fn get_index_by_uuid(arr: &[Command], uuid: &str) -> Option<usize> {
+    arr.iter().position(|ref r| r.id == uuid)
 }
 
 // Reducer Functions
@@ -22,21 +24,52 @@ fn get_index_by_uuid(arr: &[Command], uuid: &str) -> usize {
 //     )
 // }
 
-pub fn do_nothing() -> Box<ReducerFn> {
-    Box::new(move |state: AppState, _action: &AppAction| -> Result<AppState, String> { Ok(state) })
+pub fn enqueue() -> Box<ReducerFn> {
+    Box::new(
+        move |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandEnqueue { uuid, cmd_str } => {
+                    Arc::make_mut(&mut state.cmd_str_queue).insert(uuid.clone(), cmd_str.clone());
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+// The command middleware runs the command before this, the entries left are
+// those of commands that started no process, `:alert` or `:every` say.
+pub fn consume() -> Box<ReducerFn> {
+    Box::new(
+        move |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandConsume(uuid) => {
+                    if state.cmd_str_queue.contains_key(uuid) {
+                        Arc::make_mut(&mut state.cmd_str_queue).remove(uuid);
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
 }
 
 pub fn create(failed: bool) -> Box<ReducerFn> {
     Box::new(
         move |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
-                AppAction::CommandCreate(uuid) | AppAction::CommandInvalid(uuid) => {
-                    match state.cmd_str_queue.remove(uuid) {
+                AppAction::CommandCreate(uuid) | AppAction::CommandInvalid { uuid, .. } => {
+                    match Arc::make_mut(&mut state.cmd_str_queue).remove(uuid) {
                         Some(cmd_str) => {
                             let cmd_obj = Command::new(cmd_str, uuid.clone(), failed);
                             match &failed {
-                                true => state.cmd_ended.push(cmd_obj),
-                                false => state.cmd_running.push(cmd_obj),
+                                true => {
+                                    Arc::make_mut(&mut state.cmd_ended).push(cmd_obj);
+                                    state.prune_ended();
+                                }
+                                false => Arc::make_mut(&mut state.cmd_running).push(cmd_obj),
                             }
                         }
                         None => {
@@ -60,10 +93,57 @@ pub fn end(_uuid: String, success: bool) -> Box<ReducerFn> {
                     success: _,
                     reason: _,
                 } => {
-                    let cmd_str_index = get_index_by_uuid(&state.cmd_running, uuid);
-                    let mut cmd = state.cmd_running.remove(cmd_str_index);
+                    // a `:wait` waiter, or a process whose creation was
+                    // never reduced
+                    let cmd_str_index = match get_index_by_uuid(&state.cmd_running, uuid) {
+                        Some(index) => index,
+                        None => return Ok(state),
+                    };
+                    let mut cmd = Arc::make_mut(&mut state.cmd_running).remove(cmd_str_index);
                     cmd.failed = !success;
-                    state.cmd_ended.push(cmd);
+                    Arc::make_mut(&mut state.cmd_ended).push(cmd);
+                    state.prune_ended();
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn focus() -> Box<ReducerFn> {
+    Box::new(
+        move |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandFocus(uuid) => {
+                    state.focused_cmd = uuid.clone();
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn output() -> Box<ReducerFn> {
+    Box::new(
+        move |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CommandOutput { uuid, line, stderr } => {
+                    let entry = match stderr {
+                        true => json!({ "text": line, "fg": "red" }),
+                        false => Value::String(line.clone()),
+                    };
+                    state.push_output(Some(uuid.as_str()), entry);
+                    Ok(state)
+                }
+                AppAction::CommandOutputClear(uuid) => {
+                    if let Some(output) = Arc::make_mut(&mut state.cmd_output).get_mut(uuid) {
+                        output.clear();
+                    }
+                    if state.tabs.selected_uuid() == Some(uuid) {
+                        state.selection = None;
+                    }
                     Ok(state)
                 }
                 _ => Ok(state),
@@ -71,3 +151,33 @@ pub fn end(_uuid: String, success: bool) -> Box<ReducerFn> {
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ended(uuid: &str) -> AppAction {
+        AppAction::CommandEnd {
+            uuid: uuid.to_string(),
+            success: true,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn ends_running_commands() {
+        let mut state = AppState::new();
+        let cmd = Command::new(String::from("exec true"), String::from("1"), false);
+        Arc::make_mut(&mut state.cmd_running).push(cmd);
+        let state = end(String::from("1"), true)(state, &ended("1")).expect("reduced");
+        assert!(state.cmd_running.is_empty());
+        assert_eq!(state.cmd_ended.len(), 1);
+    }
+
+    #[test]
+    fn ignores_unknown_commands() {
+        let state = end(String::from("2"), true)(AppState::new(), &ended("2")).expect("reduced");
+        assert!(state.cmd_running.is_empty());
+        assert!(state.cmd_ended.is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use actions::AppAction;
+use reducers::command_bar::set_line;
+use reducers::ReducerFn;
+use structs::app::AppState;
+
+pub fn set() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CompletionSet(completion) => {
+                    state.completion = completion.clone();
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn select() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::CompletionSelect(step) => {
+                    let line = match state.completion {
+                        Some(ref mut completion) => completion
+                            .select(*step)
+                            .cloned()
+                            .map(|candidate| completion.line_with(&candidate)),
+                        None => None,
+                    };
+                    if let Some(line) = line {
+                        set_line(&mut state, format!(":{}", line));
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
@@ -1,6 +1,6 @@
 use actions::AppAction;
 use reducers::ReducerFn;
-use serde_json::Value;
+use serde_json::{json, Value};
 use structs::app::AppState;
 
 pub fn push() -> Box<ReducerFn> {
@@ -8,12 +8,36 @@ pub fn push() -> Box<ReducerFn> {
         |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
                 AppAction::ConsolePush(line) => {
-                    let value = state.json_store["console_output_lines"]
-                        .as_array()
-                        .expect("command is not array");
-                    let mut process_value = value.to_vec();
-                    process_value.push(Value::String(line.to_string()));
-                    state.json_store["console_output_lines"] = Value::Array(process_value);
+                    state.push_output(None, Value::String(line.to_string()));
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn highlight() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::ConsoleHighlight { line, color } => {
+                    state.push_output(None, json!({ "text": line, "fg": color }));
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn scrollback() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                // outputs longer than that shrink on their next line
+                AppAction::ScrollbackSet(lines) => {
+                    state.scrollback = *lines;
                     Ok(state)
                 }
                 _ => Ok(state),
@@ -0,0 +1,101 @@
+use actions::AppAction;
+use reducers::command_bar::set_line;
+use reducers::ReducerFn;
+use std::sync::Arc;
+use structs::app::AppState;
+
+pub fn load() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::HistoryLoad(entries) => {
+                    // lines entered before the file was read stay the newest
+                    let mut loaded = entries.clone();
+                    loaded.extend(state.history.entries.iter().cloned());
+                    state.history.entries = Arc::new(loaded);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn recall() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            let current = state.json_store["command"]
+                .as_str()
+                .expect("command is not str")
+                .chars()
+                .skip(1)
+                .collect::<String>();
+            let recalled = match action {
+                AppAction::HistoryPrev => state.history.older(&current).cloned(),
+                AppAction::HistoryNext => state.history.newer().cloned(),
+                _ => None,
+            };
+            if let Some(line) = recalled {
+                set_line(&mut state, format!(":{}", line));
+            }
+            Ok(state)
+        },
+    )
+}
+
+// While searching the command bar shows the query and its match, the line
+// being edited is kept in the history draft.
+pub fn search() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::HistorySearch(query) => {
+                    if state.history.search.is_none() {
+                        state.history.draft = state.json_store["command"]
+                            .as_str()
+                            .expect("command is not str")
+                            .chars()
+                            .skip(1)
+                            .collect();
+                    }
+                    let len = state.history.entries.len();
+                    state.history.search_match = state.history.find(query, len);
+                    state.history.search = Some(query.clone());
+                }
+                AppAction::HistorySearchNext => {
+                    let query = state.history.search.clone().unwrap_or_default();
+                    let before = state
+                        .history
+                        .search_match
+                        .unwrap_or(state.history.entries.len());
+                    // stay on the oldest match once there are no more
+                    if let Some(found) = state.history.find(&query, before) {
+                        state.history.search_match = Some(found);
+                    }
+                }
+                AppAction::HistorySearchEnd(accept) => {
+                    let line = match (accept, state.history.search_match) {
+                        (true, Some(found)) => state.history.entries[found].clone(),
+                        _ => state.history.draft.clone(),
+                    };
+                    state.history.reset();
+                    set_line(&mut state, format!(":{}", line));
+                    return Ok(state);
+                }
+                _ => return Ok(state),
+            }
+            let found = state
+                .history
+                .search_match
+                .map(|found| state.history.entries[found].clone())
+                .unwrap_or_default();
+            let prompt = format!(
+                "(reverse-i-search)`{}': {}",
+                state.history.search.clone().unwrap_or_default(),
+                found
+            );
+            set_line(&mut state, prompt);
+            Ok(state)
+        },
+    )
+}
@@ -1,16 +1,23 @@
+mod alerts;
 mod command_bar;
 pub mod commands;
+mod completion;
 mod console;
 mod error;
+mod history;
 mod keyboard;
 mod mode;
+mod palette;
+mod panes;
+mod quotes;
+mod tabs;
+mod ticket;
 
 use actions::AppAction;
 use redux::Reducer;
 use structs::app::AppState;
 
 pub type ReducerFn = dyn Fn(AppState, &AppAction) -> Result<AppState, String>;
-pub type CommandGen = fn() -> Box<ReducerFn>;
 type ReducerArray = Vec<Box<ReducerFn>>;
 
 impl Reducer for AppState {
@@ -21,13 +28,61 @@ impl Reducer for AppState {
         debug!("REDUCED {:?}", &action);
         let reducers: ReducerArray = match action {
             AppAction::SetMode(_) => vec![mode::set()],
+            AppAction::KeymapSet(_) => vec![mode::keymap()],
+            AppAction::KeyPending(_) => vec![mode::pending()],
             AppAction::ConsolePush(_) => vec![console::push()],
+            AppAction::ConsoleHighlight { .. } => vec![console::highlight()],
+            AppAction::ScrollbackSet(_) => vec![console::scrollback()],
             AppAction::CommandBarPush(_) => vec![command_bar::push()],
             AppAction::CommandBarPop(_) => vec![command_bar::pop()],
+            AppAction::CommandBarDelete(_) => vec![command_bar::delete()],
+            AppAction::CommandBarMove(_) => vec![command_bar::move_cursor()],
+            AppAction::CommandBarKill(_) => vec![command_bar::kill()],
+            AppAction::CommandBarYank => vec![command_bar::yank()],
+            AppAction::Yank(_) => vec![command_bar::copy()],
             AppAction::CommandBarSet(_) => vec![command_bar::set()],
             AppAction::CommandBarEnqueueCmd(_) => vec![command_bar::enqueue_cmd()],
+            AppAction::CompletionSet(_) => vec![completion::set()],
+            AppAction::CompletionSelect(_) => vec![completion::select()],
+            AppAction::PaletteSet(_) | AppAction::PaletteAccept => vec![palette::set()],
+            AppAction::PaletteQuery(_) | AppAction::PaletteSelect(_) => vec![palette::filter()],
+            AppAction::HistoryLoad(_) => vec![history::load()],
+            AppAction::HistoryPrev | AppAction::HistoryNext => vec![history::recall()],
+            AppAction::HistorySearch(_)
+            | AppAction::HistorySearchNext
+            | AppAction::HistorySearchEnd(_) => vec![history::search()],
+            AppAction::QuoteUpdate(_) => vec![quotes::update()],
+            AppAction::MarketsSet(_) => vec![quotes::markets()],
+            AppAction::AlertCreate(_) => vec![alerts::create()],
+            AppAction::AlertTrigger(_) => vec![alerts::trigger()],
+            AppAction::AlertRemove(_) => vec![alerts::remove()],
+            AppAction::AlertFlashClear(_) => vec![alerts::flash_clear()],
+            AppAction::CommandEnqueue { .. } => vec![commands::enqueue()],
+            AppAction::CommandConsume(_) => vec![commands::consume()],
+            AppAction::CommandFocus(_) => vec![commands::focus()],
+            AppAction::CommandOutput { .. } => vec![commands::output()],
+            AppAction::CommandOutputClear(_) => vec![commands::output()],
+            AppAction::TabOpen(_) => vec![tabs::open()],
+            AppAction::TabClose(_) => vec![tabs::close()],
+            AppAction::TabNext | AppAction::TabPrevious | AppAction::TabSelect(_) => {
+                vec![tabs::switch()]
+            }
+            AppAction::Scroll(_) => vec![panes::scroll()],
+            AppAction::PaneFocus(_) | AppAction::PaneFocusNext | AppAction::MarketSelect(_) => {
+                vec![panes::focus()]
+            }
+            AppAction::SelectionMove(_) => vec![panes::select()],
+            AppAction::Resize(..) => vec![panes::resize()],
+            AppAction::TicketSet(_) => vec![ticket::set()],
+            AppAction::TicketSelect(_)
+            | AppAction::TicketInput(_)
+            | AppAction::TicketErase
+            | AppAction::TicketSide
+            | AppAction::TicketSubmit => vec![ticket::edit()],
+            AppAction::RiskSet(_) => vec![ticket::risk()],
+            AppAction::StdinSend { .. } => vec![command_bar::stdin_sent()],
             AppAction::CommandCreate(_) => vec![commands::create(false)],
-            AppAction::CommandInvalid(_) => vec![commands::create(true)],
+            AppAction::CommandInvalid { .. } => vec![commands::create(true)],
             AppAction::CommandEnd {
                 ref uuid,
                 success,
@@ -43,6 +98,7 @@ impl Reducer for AppState {
             // }
             _ => vec![],
         };
+        // the large collections are behind `Arc`, this clone copies none of them
         let _state = combined_reducer(reducers)(self.clone(), &action).unwrap();
         Ok(_state)
     }
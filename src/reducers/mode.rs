@@ -1,13 +1,65 @@
 use actions::AppAction;
 use reducers::ReducerFn;
-use structs::app::AppState;
+use std::sync::Arc;
+use structs::app::{AppState, ModeCategory, ModeSpec};
+use structs::ui::Selection;
 
 pub fn set() -> Box<ReducerFn> {
     Box::new(
         |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
             match action {
                 AppAction::SetMode(mode) => {
-                    state.json_store["mode"] = mode.clone();
+                    // unknown modes are reported by the command bar middleware
+                    let category = match ModeSpec::of(mode) {
+                        Some(spec) => spec.category,
+                        None => return Ok(state),
+                    };
+                    Arc::make_mut(&mut state.json_store)["mode"] = mode.clone();
+                    state.history.reset();
+                    // only input mode is bound to a running command
+                    if category != ModeCategory::Input {
+                        state.focused_cmd = None;
+                    }
+                    // visual mode starts on the last line in view
+                    state.selection = match category {
+                        ModeCategory::Visual => {
+                            let len = state.output_lines().len();
+                            let offset = state.tabs.selected_scroll();
+                            len.checked_sub(offset + 1).map(Selection::new)
+                        }
+                        _ => None,
+                    };
+                    if category != ModeCategory::Ticket {
+                        state.ticket = None;
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn keymap() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::KeymapSet(keymap) => {
+                    state.keymap = Arc::new(keymap.clone());
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn pending() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::KeyPending(keys) => {
+                    state.pending_keys = keys.clone();
                     Ok(state)
                 }
                 _ => Ok(state),
@@ -0,0 +1,36 @@
+use actions::AppAction;
+use reducers::ReducerFn;
+use structs::app::AppState;
+
+pub fn set() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::PaletteSet(palette) => {
+                    state.palette = palette.clone();
+                    Ok(state)
+                }
+                AppAction::PaletteAccept => {
+                    state.palette = None;
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn filter() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            if let Some(ref mut palette) = state.palette {
+                match action {
+                    AppAction::PaletteQuery(query) => palette.set_query(query.clone()),
+                    AppAction::PaletteSelect(step) => palette.select(*step),
+                    _ => {}
+                }
+            }
+            Ok(state)
+        },
+    )
+}
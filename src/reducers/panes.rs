@@ -0,0 +1,131 @@
+use actions::AppAction;
+use reducers::ReducerFn;
+use structs::app::AppState;
+use structs::ui::Pane;
+
+pub fn scroll() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::Scroll(scroll) => {
+                    let page = state.page_height();
+                    match state.panes.focused {
+                        Pane::Output => {
+                            let len = state.output_lines().len();
+                            // offsets count from the end, positions from the top
+                            let max_offset = len.saturating_sub(page);
+                            let offset = state.tabs.selected_scroll().min(max_offset);
+                            let position = scroll.target(max_offset - offset, max_offset + 1, page);
+                            let selection = state.tabs.selection;
+                            state.tabs.scroll[selection] = max_offset - position;
+                        }
+                        Pane::Markets => {
+                            let len = state.quotes.len();
+                            state.panes.market = scroll.target(state.panes.market, len, page);
+                        }
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn focus() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            let markets_shown = !state.quotes.is_empty();
+            match action {
+                AppAction::PaneFocus(Pane::Markets) if !markets_shown => {}
+                AppAction::PaneFocus(pane) => state.panes.focused = *pane,
+                AppAction::PaneFocusNext => state.panes.next(markets_shown),
+                AppAction::MarketSelect(row) if *row < state.quotes.len() => {
+                    state.panes.focused = Pane::Markets;
+                    state.panes.market = *row;
+                }
+                _ => {}
+            }
+            Ok(state)
+        },
+    )
+}
+
+pub fn select() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::SelectionMove(scroll) => {
+                    let page = state.page_height();
+                    let len = state.output_lines().len();
+                    if let Some(ref mut selection) = state.selection {
+                        // the output may have been cleared under the selection
+                        let last = len.saturating_sub(1);
+                        selection.anchor = selection.anchor.min(last);
+                        selection.cursor = scroll.target(selection.cursor, len, page);
+                        // scroll just enough to keep the cursor in view
+                        let end = len - state.tabs.selected_scroll().min(len);
+                        let offset = if selection.cursor >= end {
+                            last - selection.cursor
+                        } else if selection.cursor + page < end {
+                            len.saturating_sub(selection.cursor + page)
+                        } else {
+                            len - end
+                        };
+                        let index = state.tabs.selection;
+                        state.tabs.scroll[index] = offset;
+                    }
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn resize() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::Resize(cols, rows) => {
+                    state.size = (*cols, *rows);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use structs::ui::{Scroll, Selection};
+
+    fn moved(state: AppState, scroll: Scroll) -> AppState {
+        select()(state, &AppAction::SelectionMove(scroll)).expect("reduced")
+    }
+
+    #[test]
+    fn selection_survives_a_cleared_output() {
+        let mut state = AppState::new();
+        state.selection = Some(Selection::new(5));
+        let state = moved(state, Scroll::LineUp);
+        assert_eq!(state.selection, Some(Selection::new(0)));
+        assert_eq!(state.tabs.selected_scroll(), 0);
+    }
+
+    #[test]
+    fn selection_scrolls_to_the_cursor() {
+        let mut state = AppState::new();
+        state.size = (80, 13);
+        for line in 0..30 {
+            state.push_output(None, Value::String(line.to_string()));
+        }
+        state.selection = Some(Selection::new(29));
+        let state = moved(state, Scroll::Top);
+        assert_eq!(state.selection.map(|s| s.cursor), Some(0));
+        assert_eq!(state.tabs.selected_scroll(), 20);
+    }
+}
@@ -0,0 +1,32 @@
+use actions::AppAction;
+use reducers::ReducerFn;
+use std::sync::Arc;
+use structs::app::AppState;
+
+pub fn update() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::QuoteUpdate(quote) => {
+                    Arc::make_mut(&mut state.quotes).insert(quote.symbol.clone(), quote.clone());
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn markets() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::MarketsSet(markets) => {
+                    state.markets = Arc::new(markets.clone());
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
@@ -0,0 +1,58 @@
+use actions::AppAction;
+use reducers::ReducerFn;
+use structs::app::AppState;
+
+pub fn open() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::TabOpen(uuid) => {
+                    // "exec curl ..." -> "curl 1a2b3c4d"
+                    let name = state
+                        .cmd_running
+                        .iter()
+                        .chain(state.cmd_ended.iter())
+                        .find(|cmd| &cmd.id == uuid)
+                        .and_then(|cmd| cmd.name.split_whitespace().nth(1).map(String::from))
+                        .unwrap_or_default();
+                    let short_id: String = uuid.chars().take(8).collect();
+                    let title = format!("{} {}", name, short_id).trim().to_string();
+                    state.tabs.open(title, uuid);
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn close() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::TabClose(index) => {
+                    state.tabs.close(*index);
+                    state.selection = None;
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn switch() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::TabNext => state.tabs.next(),
+                AppAction::TabPrevious => state.tabs.previous(),
+                AppAction::TabSelect(index) => state.tabs.select(*index),
+                _ => return Ok(state),
+            }
+            // the selection was in the output of the previous tab
+            state.selection = None;
+            Ok(state)
+        },
+    )
+}
@@ -0,0 +1,50 @@
+use actions::AppAction;
+use reducers::ReducerFn;
+use structs::app::AppState;
+
+pub fn set() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::TicketSet(ticket) => {
+                    state.ticket = ticket.clone();
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
+
+pub fn edit() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            if let Some(ref mut ticket) = state.ticket {
+                match action {
+                    AppAction::TicketSelect(step) => ticket.select(*step),
+                    AppAction::TicketInput(c) => ticket.push(*c),
+                    AppAction::TicketErase => ticket.pop(),
+                    AppAction::TicketSide => ticket.toggle_side(),
+                    // a valid ticket is placed and closed by the command middleware
+                    AppAction::TicketSubmit => ticket.error = ticket.order(&state.risk).err(),
+                    _ => {}
+                }
+            }
+            Ok(state)
+        },
+    )
+}
+
+pub fn risk() -> Box<ReducerFn> {
+    Box::new(
+        |mut state: AppState, action: &AppAction| -> Result<AppState, String> {
+            match action {
+                AppAction::RiskSet(limits) => {
+                    state.risk = limits.clone();
+                    Ok(state)
+                }
+                _ => Ok(state),
+            }
+        },
+    )
+}
@@ -0,0 +1,90 @@
+mod native;
+mod python;
+
+pub use self::native::LibraryStrategy;
+pub use self::python::PythonStrategy;
+
+use std::fs;
+use std::path::Path;
+use structs::app::{Order, Quote};
+
+/// A strategy compiled into the binary or loaded from a dynamic library.
+///
+/// The hooks mirror the Python runtime: `on_quotes` receives the same quotes
+/// a Python script's `on_quotes(quotes)` gets and returns the same `i64`
+/// signal, so both kinds go through the same reporting in
+/// `StrategyMiddleWare`.
+pub trait Strategy: Send {
+    fn name(&self) -> String;
+
+    fn on_start(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn on_quotes(&mut self, quotes: &[Quote]) -> Result<i64, String>;
+
+    /// Orders to place after `on_quotes`, checked against the risk limits
+    /// and routed through `:order` like a ticket.
+    fn orders(&mut self) -> Result<Vec<Order>, String> {
+        Ok(Vec::new())
+    }
+}
+
+pub struct StrategyRegistry {
+    strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> StrategyRegistry {
+        StrategyRegistry {
+            strategies: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, mut strategy: Box<dyn Strategy>) -> Result<(), String> {
+        strategy.on_start()?;
+        self.strategies.push(strategy);
+        Ok(())
+    }
+
+    /// Registers every `.py` script and dynamic library found in `dir`,
+    /// returning one message per file that failed to load.
+    pub fn load_dir(&mut self, dir: &Path) -> Vec<String> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+        let mut errors = Vec::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let loaded = match path.extension().and_then(|ext| ext.to_str()) {
+                Some("py") => PythonStrategy::load(&path).map(|s| Box::new(s) as Box<dyn Strategy>),
+                Some("so") | Some("dylib") | Some("dll") => {
+                    LibraryStrategy::load(&path).map(|s| Box::new(s) as Box<dyn Strategy>)
+                }
+                _ => continue,
+            };
+            if let Err(err) = loaded.and_then(|s| self.register(s)) {
+                errors.push(format!("{}: {}", path.display(), err));
+            }
+        }
+        errors
+    }
+
+    /// The signal and the orders of each strategy.
+    pub fn run(&mut self, quotes: &[Quote]) -> Vec<(String, Result<(i64, Vec<Order>), String>)> {
+        self.strategies
+            .iter_mut()
+            .map(|s| {
+                let result = s
+                    .on_quotes(quotes)
+                    .and_then(|signal| Ok((signal, s.orders()?)));
+                (s.name(), result)
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty()
+    }
+}
@@ -0,0 +1,63 @@
+use libloading::{Library, Symbol};
+use std::path::Path;
+use strategies::Strategy;
+use structs::app::{Order, Quote};
+
+/// Symbol a strategy library must export:
+///
+/// ```ignore
+/// #[no_mangle]
+/// pub extern "C" fn _polorust_strategy_create() -> *mut Box<dyn Strategy> {
+///     Box::into_raw(Box::new(Box::new(MyStrategy::default())))
+/// }
+/// ```
+///
+/// A `*mut dyn Strategy` is a fat pointer, which has no C layout, hence the
+/// thin pointer to a box. The library has to be built with the same compiler
+/// and `Strategy` trait.
+const CONSTRUCTOR: &'static [u8] = b"_polorust_strategy_create";
+
+type Constructor = unsafe extern "C" fn() -> *mut Box<dyn Strategy>;
+
+pub struct LibraryStrategy {
+    // declared before `_lib` so it is dropped while the library is loaded
+    strategy: Box<dyn Strategy>,
+    _lib: Library,
+}
+
+impl LibraryStrategy {
+    pub fn load(path: &Path) -> Result<LibraryStrategy, String> {
+        let lib = Library::new(path).map_err(|e| e.to_string())?;
+        let strategy = unsafe {
+            let constructor: Symbol<Constructor> =
+                lib.get(CONSTRUCTOR).map_err(|e| e.to_string())?;
+            let strategy = constructor();
+            if strategy.is_null() {
+                return Err(String::from("No strategy created"));
+            }
+            *Box::from_raw(strategy)
+        };
+        Ok(LibraryStrategy {
+            strategy,
+            _lib: lib,
+        })
+    }
+}
+
+impl Strategy for LibraryStrategy {
+    fn name(&self) -> String {
+        self.strategy.name()
+    }
+
+    fn on_start(&mut self) -> Result<(), String> {
+        self.strategy.on_start()
+    }
+
+    fn on_quotes(&mut self, quotes: &[Quote]) -> Result<i64, String> {
+        self.strategy.on_quotes(quotes)
+    }
+
+    fn orders(&mut self) -> Result<Vec<Order>, String> {
+        self.strategy.orders()
+    }
+}
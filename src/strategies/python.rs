@@ -0,0 +1,78 @@
+use cpython::{NoArgs, PyDict, PyObject, Python};
+use std::fs;
+use std::path::Path;
+use strategies::Strategy;
+use structs::app::{Order, Quote, Side};
+use utils::python;
+
+/// A Python script defining `on_quotes(quotes)`, and optionally `on_start()`
+/// and `orders()`, the hooks of `Strategy`. Orders are returned as
+/// `(side, symbol, amount, price)` tuples. The script runs once, in a module
+/// of its own, so its globals carry over from one call to the next.
+pub struct PythonStrategy {
+    name: String,
+    globals: PyDict,
+}
+
+impl PythonStrategy {
+    pub fn new(name: &str, code: &str) -> Result<PythonStrategy, String> {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let globals = python::load(py, name, code).map_err(|e| format!("{:?}", e))?;
+        if globals.get_item(py, "on_quotes").is_none() {
+            return Err(String::from("on_quotes(quotes) is not defined"));
+        }
+        Ok(PythonStrategy {
+            name: name.to_string(),
+            globals,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<PythonStrategy, String> {
+        let code = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        PythonStrategy::new(&name, &code)
+    }
+}
+
+impl Strategy for PythonStrategy {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn on_start(&mut self) -> Result<(), String> {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        python::call::<_, PyObject>(py, &self.globals, "on_start", NoArgs)
+            .map(|_| ())
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    fn on_quotes(&mut self, quotes: &[Quote]) -> Result<i64, String> {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        python::call(py, &self.globals, "on_quotes", (quotes.to_vec(),))
+            .map_err(|e| format!("{:?}", e))?
+            .ok_or_else(|| String::from("on_quotes(quotes) is not defined"))
+    }
+
+    fn orders(&mut self) -> Result<Vec<Order>, String> {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let orders: Vec<(String, String, f64, f64)> =
+            python::call(py, &self.globals, "orders", NoArgs)
+                .map_err(|e| format!("{:?}", e))?
+                .unwrap_or_default();
+        orders
+            .into_iter()
+            .map(|(side, symbol, amount, price)| {
+                let side = Side::parse(&side)
+                    .ok_or_else(|| format!("Invalid side {:?}, buy or sell", side))?;
+                Order::new(side, &symbol, amount, price)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use structs::app::Quote;
+use utils::shell::{self, Token};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AlertMetric {
+    Price,
+    Spread,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Comparison {
+    Above,
+    AboveOrEqual,
+    Below,
+    BelowOrEqual,
+}
+
+impl Comparison {
+    fn parse(op: &str) -> Result<Comparison, String> {
+        match op {
+            ">" => Ok(Comparison::Above),
+            ">=" => Ok(Comparison::AboveOrEqual),
+            "<" => Ok(Comparison::Below),
+            "<=" => Ok(Comparison::BelowOrEqual),
+            &_ => Err(format!("Unknown operator {:?}", op)),
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::AboveOrEqual => ">=",
+            Comparison::Below => "<",
+            Comparison::BelowOrEqual => "<=",
+        }
+    }
+
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::AboveOrEqual => value >= threshold,
+            Comparison::Below => value < threshold,
+            Comparison::BelowOrEqual => value <= threshold,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: usize,
+    pub symbol: String,
+    pub metric: AlertMetric,
+    pub op: Comparison,
+    pub threshold: f64,
+    pub percent: bool,
+    /// Process started on trigger, the arguments of an `:exec`.
+    pub exec: Option<String>,
+    pub triggered: bool,
+}
+
+impl Alert {
+    /// Parses the arguments of `:alert`, either
+    /// `BTC_ETH > 0.032 [exec <cmd>]` or `spread BTC_ETH > 0.5% [exec <cmd>]`.
+    pub fn parse(id: usize, args: &[Token]) -> Result<Alert, String> {
+        let usage = || String::from("Usage: alert [spread] SYMBOL OP VALUE [exec CMD]");
+        let (metric, rest) = match args.first().map(|arg| arg.as_str()) {
+            Some("spread") => (AlertMetric::Spread, &args[1..]),
+            Some(_) => (AlertMetric::Price, args),
+            None => return Err(usage()),
+        };
+        if rest.len() < 3 {
+            return Err(usage());
+        }
+        let op = Comparison::parse(rest[1].as_str())?;
+        let value = rest[2].as_str();
+        let percent = value.ends_with('%');
+        if percent && metric == AlertMetric::Price {
+            return Err(String::from(
+                "Percentages are only supported for spread alerts",
+            ));
+        }
+        let threshold = value
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid value {:?}", value))?;
+        let exec = match rest.get(3).map(|arg| arg.as_str()) {
+            Some("exec") if rest.len() > 4 => Some(shell::join(&rest[4..])),
+            Some("exec") => return Err(String::from("Missing command after exec")),
+            Some(other) => return Err(format!("Unexpected argument {:?}", other)),
+            None => None,
+        };
+        Ok(Alert {
+            id,
+            symbol: rest[0].as_str().to_uppercase(),
+            metric,
+            op,
+            threshold,
+            percent,
+            exec,
+            triggered: false,
+        })
+    }
+
+    /// The value the condition is checked against, for this quote, once
+    /// both sides of its book have orders.
+    pub fn value(&self, quote: &Quote) -> Option<f64> {
+        match (&self.metric, self.percent) {
+            (AlertMetric::Price, _) => quote.mid(),
+            (AlertMetric::Spread, false) => quote.spread(),
+            (AlertMetric::Spread, true) => quote.spread_pct(),
+        }
+    }
+
+    pub fn evaluate(&self, quote: &Quote) -> bool {
+        let holds = |value| self.op.holds(value, self.threshold);
+        !self.triggered && quote.symbol == self.symbol && self.value(quote).map_or(false, holds)
+    }
+
+    pub fn describe(&self) -> String {
+        let metric = match self.metric {
+            AlertMetric::Price => "",
+            AlertMetric::Spread => "spread ",
+        };
+        let unit = match self.percent {
+            true => "%",
+            false => "",
+        };
+        format!(
+            "{}{} {} {}{}",
+            metric,
+            self.symbol,
+            self.op.symbol(),
+            self.threshold,
+            unit
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Alert, String> {
+        Alert::parse(1, &shell::tokenize(line).expect("tokenized"))
+    }
+
+    #[test]
+    fn parses_every_comparison() {
+        for (line, op) in &[
+            ("BTC_ETH > 0.032", Comparison::Above),
+            ("BTC_ETH >= 0.032", Comparison::AboveOrEqual),
+            ("BTC_ETH < 0.032", Comparison::Below),
+            ("BTC_ETH <= 0.032", Comparison::BelowOrEqual),
+        ] {
+            let alert = parse(line).expect(line);
+            assert_eq!(&alert.op, op);
+            assert_eq!(alert.threshold, 0.032);
+            assert_eq!(alert.symbol, "BTC_ETH");
+        }
+    }
+
+    #[test]
+    fn parses_spread_percentages() {
+        let alert = parse("spread btc_eth >= 0.5%").expect("alert");
+        assert_eq!(alert.metric, AlertMetric::Spread);
+        assert!(alert.percent);
+        assert_eq!(alert.describe(), "spread BTC_ETH >= 0.5%");
+        assert!(parse("BTC_ETH > 0.5%").is_err());
+    }
+
+    #[test]
+    fn keeps_the_process_to_exec() {
+        let alert = parse("spread BTC_ETH > 0.5% exec notify-send 'wide spread'").expect("alert");
+        assert_eq!(alert.exec, Some(String::from("notify-send 'wide spread'")));
+    }
+
+    fn quote(bid_price: f64, ask_price: f64) -> Quote {
+        Quote {
+            symbol: String::from("BTC_ETH"),
+            bid_price,
+            ask_price,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn evaluates_quotes_with_both_sides() {
+        let alert = parse("BTC_ETH > 0.03").expect("alert");
+        assert!(alert.evaluate(&quote(0.031, 0.033)));
+        assert!(!alert.evaluate(&quote(0.029, 0.03)));
+        // half of the ask alone would be no price at all
+        assert!(!alert.evaluate(&quote(0.0, 0.07)));
+        assert_eq!(alert.value(&quote(0.07, 0.0)), None);
+
+        let spread = parse("spread BTC_ETH > 1%").expect("alert");
+        assert!(spread.evaluate(&quote(0.99, 1.01)));
+        assert!(!spread.evaluate(&quote(0.0, 1.01)));
+    }
+
+    #[test]
+    fn rejects_bad_alerts() {
+        assert!(parse("BTC_ETH").is_err());
+        assert!(parse("BTC_ETH = 1").is_err());
+        assert!(parse("BTC_ETH > x").is_err());
+        assert!(parse("BTC_ETH > 1 exec").is_err());
+        assert!(parse("BTC_ETH > 1 then").is_err());
+    }
+}
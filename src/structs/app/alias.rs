@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use utils::shell::{self, Token};
+
+const MAX_DEPTH: usize = 16;
+// can't be redefined, or there would be no way back
+const RESERVED: [&'static str; 3] = ["alias", "macro", "unalias"];
+
+/// `:alias` and `:macro` definitions, kept in `aliases.json`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Aliases {
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Steps of each macro, `$1`, `$2`... and `$@` standing for its arguments.
+    #[serde(default)]
+    pub macros: BTreeMap<String, Vec<String>>,
+}
+
+impl Aliases {
+    pub fn load(path: &Path) -> Result<Aliases, String> {
+        let mut aliases = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str::<Aliases>(&contents)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+            Err(_) => Aliases::default(),
+        };
+        aliases.path = path.to_path_buf();
+        Ok(aliases)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&self.path, contents).map_err(|e| e.to_string())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.aliases
+            .keys()
+            .chain(self.macros.keys())
+            .cloned()
+            .collect()
+    }
+
+    /// Parses `name = body` from the raw text following `:alias` or `:macro`.
+    pub fn parse_definition(args: &str) -> Result<(String, String), String> {
+        let mut parts = args.splitn(2, '=');
+        let name = parts.next().unwrap_or("").trim();
+        let body = parts.next().map(|body| body.trim()).unwrap_or("");
+        if name.is_empty() || name.contains(char::is_whitespace) || body.is_empty() {
+            return Err(String::from("Usage: alias|macro NAME = COMMAND"));
+        }
+        if RESERVED.contains(&name) {
+            return Err(format!("{:?} can't be redefined", name));
+        }
+        Ok((name.to_string(), body.to_string()))
+    }
+
+    pub fn define_alias(&mut self, name: String, body: String) -> Result<(), String> {
+        self.macros.remove(&name);
+        self.aliases.insert(name, body);
+        self.save()
+    }
+
+    /// Steps are separated by `;`.
+    pub fn define_macro(&mut self, name: String, body: String) -> Result<(), String> {
+        let steps: Vec<String> = body
+            .split(';')
+            .map(|step| step.trim().trim_start_matches(':').to_string())
+            .filter(|step| !step.is_empty())
+            .collect();
+        self.aliases.remove(&name);
+        self.macros.insert(name, steps);
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        let removed = self.aliases.remove(name).is_some() || self.macros.remove(name).is_some();
+        match removed {
+            true => self.save(),
+            false => Err(format!("No alias or macro {:?}", name)),
+        }
+    }
+
+    /// Replaces a leading alias in `line` by its definition, repeatedly.
+    pub fn resolve(&self, line: &str) -> Result<String, String> {
+        let mut line = line.trim_start().to_string();
+        for _ in 0..MAX_DEPTH {
+            let name = line.split_whitespace().next().unwrap_or("").to_string();
+            match self.aliases.get(&name) {
+                Some(body) => line = format!("{}{}", body, &line[name.len()..]),
+                None => return Ok(line),
+            }
+        }
+        Err(String::from("Alias nested too deep, is it recursive?"))
+    }
+
+    /// The command lines run by macro `name` with arguments `args`.
+    pub fn expand_macro(&self, name: &str, args: &[Token]) -> Option<Result<Vec<String>, String>> {
+        let steps = self.macros.get(name)?;
+        let mut expanded = Vec::new();
+        for step in steps {
+            match substitute(step, args) {
+                Ok(step) => expanded.push(step),
+                Err(err) => return Some(Err(format!("{}: {}", name, err))),
+            }
+        }
+        Some(Ok(expanded))
+    }
+}
+
+// `$N` becomes the quoted Nth argument and `$@` all of them, `\$` is kept
+// for the tokenizer to turn into a literal `$`.
+fn substitute(step: &str, args: &[Token]) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = step.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                result.push(c);
+                if let Some(next) = chars.next() {
+                    result.push(next);
+                }
+            }
+            '$' if chars.peek() == Some(&'@') => {
+                chars.next();
+                result.push_str(&shell::join(args));
+            }
+            '$' if chars.peek().map_or(false, |c| c.is_ascii_digit()) => {
+                let mut number = String::new();
+                while let Some(&digit) = chars.peek() {
+                    if !digit.is_ascii_digit() {
+                        break;
+                    }
+                    number.push(digit);
+                    chars.next();
+                }
+                let index = number.parse::<usize>().unwrap_or(0);
+                match index.checked_sub(1).and_then(|index| args.get(index)) {
+                    Some(arg) => result.push_str(&shell::join(&[arg.clone()])),
+                    None => return Err(format!("Missing argument ${}", index)),
+                }
+            }
+            c => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<Token> {
+        words
+            .iter()
+            .map(|word| Token::Word(word.to_string()))
+            .collect()
+    }
+
+    fn aliases(aliases: &[(&str, &str)]) -> Aliases {
+        let mut defined = Aliases::default();
+        for (name, body) in aliases {
+            defined.aliases.insert(name.to_string(), body.to_string());
+        }
+        defined
+    }
+
+    #[test]
+    fn parses_definitions() {
+        assert_eq!(
+            Aliases::parse_definition(" ll = exec ls -l "),
+            Ok((String::from("ll"), String::from("exec ls -l")))
+        );
+        // only the first `=` separates the name
+        assert_eq!(
+            Aliases::parse_definition("eq = exec test a = b"),
+            Ok((String::from("eq"), String::from("exec test a = b")))
+        );
+        assert!(Aliases::parse_definition("ll").is_err());
+        assert!(Aliases::parse_definition("ll =").is_err());
+        assert!(Aliases::parse_definition("= exec ls").is_err());
+        assert!(Aliases::parse_definition("l l = exec ls").is_err());
+        assert!(Aliases::parse_definition("alias = exec ls").is_err());
+    }
+
+    #[test]
+    fn resolves_leading_aliases() {
+        let aliases = aliases(&[("ll", "exec ls -l"), ("la", "ll -a")]);
+        assert_eq!(
+            aliases.resolve("  la /tmp"),
+            Ok(String::from("exec ls -l -a /tmp"))
+        );
+        assert_eq!(aliases.resolve("ll"), Ok(String::from("exec ls -l")));
+        // only the first word is an alias
+        assert_eq!(aliases.resolve("exec ll"), Ok(String::from("exec ll")));
+        // and only a whole word
+        assert_eq!(aliases.resolve("llama"), Ok(String::from("llama")));
+    }
+
+    #[test]
+    fn stops_recursive_aliases() {
+        assert!(aliases(&[("loop", "loop again")]).resolve("loop").is_err());
+        assert!(aliases(&[("a", "b"), ("b", "a")]).resolve("a").is_err());
+    }
+
+    #[test]
+    fn substitutes_arguments() {
+        let args = words(&["BTC_ETH", "a b"]);
+        assert_eq!(
+            substitute("exec echo $2 $1", &args),
+            Ok(String::from("exec echo 'a b' BTC_ETH"))
+        );
+        assert_eq!(
+            substitute("exec echo $@", &args),
+            Ok(String::from("exec echo BTC_ETH 'a b'"))
+        );
+        assert_eq!(
+            substitute("exec echo \\$1 $", &args),
+            Ok(String::from("exec echo \\$1 $"))
+        );
+        assert!(substitute("exec echo $3", &args).is_err());
+        assert!(substitute("exec echo $0", &args).is_err());
+    }
+
+    #[test]
+    fn expands_macros() {
+        let mut aliases = Aliases::default();
+        let steps = vec![String::from("ticket buy $1"), String::from("alerts")];
+        aliases.macros.insert(String::from("buy"), steps);
+        assert_eq!(
+            aliases.expand_macro("buy", &words(&["BTC_ETH"])),
+            Some(Ok(vec![
+                String::from("ticket buy BTC_ETH"),
+                String::from("alerts")
+            ]))
+        );
+        assert!(aliases.expand_macro("buy", &[]).expect("macro").is_err());
+        assert_eq!(aliases.expand_macro("sell", &[]), None);
+    }
+}
@@ -1,94 +1,437 @@
 use actions::AppAction;
-use reducers::{commands, CommandGen};
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
 use structs::app::events;
 
 // Experimental
-use std::io::{BufRead, BufReader};
-use std::process::{Command, Stdio};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ChildStdin, ExitStatus};
 use std::{panic, thread};
+use structs::app::Pipeline;
+
+const WAIT_POLL_MILLIS: u64 = 100;
+
+/// The children of a spawned pipeline, kept so they can be signalled while
+/// their output is read.
+pub struct Process {
+    pub children: Vec<Child>,
+    // lines for the stdin of the first stage, unless it reads from a file,
+    // written by a thread of its own since a child may stop reading
+    pub stdin: Option<mpsc::Sender<String>>,
+    // exit status of each child, once known
+    pub statuses: Vec<Option<ExitStatus>>,
+    // last signal sent to the children, reported when they terminate
+    pub signal: Option<String>,
+    // `:wait` commands ending along with this one
+    pub waiters: Vec<String>,
+}
+
+impl Process {
+    /// Registered before the pipeline is spawned, so that it can be found,
+    /// killed or waited for meanwhile.
+    pub fn pending() -> Process {
+        Process {
+            children: Vec::new(),
+            stdin: None,
+            statuses: Vec::new(),
+            signal: None,
+            waiters: Vec::new(),
+        }
+    }
+
+    /// Takes the spawned children, passing them a signal sent while they
+    /// were being spawned.
+    pub fn start(&mut self, mut children: Vec<Child>) {
+        self.stdin = children
+            .first_mut()
+            .and_then(|child| child.stdin.take())
+            .map(write_lines);
+        self.statuses = children.iter().map(|_| None).collect();
+        self.children = children;
+        match self.signal.as_ref().map(|signal| signal.as_str()) {
+            Some("KILL") => {
+                for child in self.children.iter_mut() {
+                    let _ = child.kill();
+                }
+            }
+            Some(signal) => {
+                if let Some(signum) = signum(signal) {
+                    for pid in self.pids() {
+                        unsafe { libc::kill(pid as libc::pid_t, signum) };
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// The exit status of the last stage, once every stage has exited.
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        for (child, status) in self.children.iter_mut().zip(self.statuses.iter_mut()) {
+            if status.is_none() {
+                *status = child.try_wait()?;
+            }
+        }
+        match self.statuses.iter().all(|status| status.is_some()) {
+            true => Ok(self.statuses.last().cloned().unwrap_or(None)),
+            false => Ok(None),
+        }
+    }
+
+    pub fn pids(&self) -> Vec<u32> {
+        self.children.iter().map(|child| child.id()).collect()
+    }
+}
 
 pub struct CommandHandler {
-    pub cmd_reg: HashMap<String, CommandGen>,
+    pub processes: Arc<Mutex<HashMap<String, Process>>>,
 }
 
 impl CommandHandler {
     pub fn new() -> CommandHandler {
         CommandHandler {
-            cmd_reg: HashMap::new(),
+            processes: Arc::new(Mutex::new(HashMap::new())),
         }
     }
+}
 
-    pub fn default() -> Self {
-        let mut handler = CommandHandler::new();
-        handler
-            .cmd_reg
-            .insert("exec".to_string(), commands::do_nothing);
-        handler
-            .cmd_reg
-            .insert("q".to_string(), commands::do_nothing);
-        handler
+impl Default for CommandHandler {
+    fn default() -> Self {
+        CommandHandler::new()
     }
 }
 
 impl CommandHandler {
-    pub fn spawn(&self, tx: mpsc::Sender<events::Event>, cmd_str: String, uuid: String) {
-        debug!( "CMD STR {:?} ", &cmd_str);
+    /// Runs `pipeline`, whose arguments were split by `utils::shell::tokenize`.
+    pub fn spawn(&self, tx: mpsc::Sender<events::Event>, pipeline: Pipeline, uuid: String) {
+        debug!("CMD STR {:?} ", &pipeline);
         let thread_tx = tx.clone();
+        let processes = self.processes.clone();
+        // registered right away, `:kill` and `:wait` may follow before the
+        // pipeline is spawned
+        processes
+            .lock()
+            .expect("Process Lock Poisoned")
+            .insert(uuid.clone(), Process::pending());
+        let pending = uuid.clone();
         let res = match thread::Builder::new().name(uuid.clone()).spawn(move || {
             // Panic Handler for Thread
             panic::set_hook(Box::new(|panic_info| {
                 error!("A panic occurred: {:?}", &panic_info);
             }));
-            let mut cmd_with_args: Vec<&str> = cmd_str.split(' ').collect();
-            let command = cmd_with_args.remove(0);
-            let res_action = match Command::new(command)
-                .args(cmd_with_args)
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    let reader = child.stdout.take().expect("Couldn't get pipe stream");
-                    let mut child_out = BufReader::new(reader);
-                    loop {
-                        let mut buffer = String::new();
-                        let read_bytes = child_out
-                            .read_line(&mut buffer)
-                            .expect("Unable to read bytes");
-                        if read_bytes != 0 {
-                            // if &buffer != "1" {
-                            //     let _ = tx.send(events::Event::Exit);
-                            // }
-                            let evt = AppAction::ConsolePush(buffer).into_event();
-                            let _ = tx.send(evt);
-                        } else {
-                            break;
-                        }
+            let res_actions = match pipeline.spawn() {
+                Ok(mut children) => {
+                    // only the last stage's stdout is left, unless redirected
+                    let stdout = children.last_mut().and_then(|child| child.stdout.take());
+                    let stderrs: Vec<_> = children
+                        .iter_mut()
+                        .filter_map(|child| child.stderr.take())
+                        .collect();
+                    if let Some(process) = processes
+                        .lock()
+                        .expect("Process Lock Poisoned")
+                        .get_mut(&uuid)
+                    {
+                        process.start(children);
+                    }
+                    let err_readers: Vec<_> = stderrs
+                        .into_iter()
+                        .map(|stderr| {
+                            let err_tx = tx.clone();
+                            let err_uuid = uuid.clone();
+                            thread::spawn(move || {
+                                read_lines(stderr, &err_tx, |line| AppAction::CommandOutput {
+                                    uuid: err_uuid.clone(),
+                                    line,
+                                    stderr: true,
+                                })
+                            })
+                        })
+                        .collect();
+                    if let Some(stdout) = stdout {
+                        read_lines(stdout, &tx, |line| AppAction::CommandOutput {
+                            uuid: uuid.clone(),
+                            line,
+                            stderr: false,
+                        });
                     }
-                    AppAction::CommandEnd {
-                        uuid: uuid.clone(),
-                        success: true,
-                        reason: String::new(),
+                    for err_reader in err_readers {
+                        let _ = err_reader.join();
                     }
+                    wait_exit(&processes, &uuid)
                 }
                 Err(error) => {
                     let err_str = format!("Child Panic: {:?}", error);
-                    AppAction::CommandEnd {
-                        uuid: uuid.clone(),
-                        success: false,
-                        reason: err_str,
-                    }
+                    let process = processes
+                        .lock()
+                        .expect("Process Lock Poisoned")
+                        .remove(&uuid);
+                    end_actions(&uuid, Err(err_str), process)
                 }
             };
-            let _ = tx.send(res_action.into_event());
+            for res_action in res_actions {
+                let _ = tx.send(res_action.into_event());
+            }
         }) {
             Ok(_result) => format_output!("green", "...", "Thread Spawned"),
-            Err(_) => format_output!("red", "!!!", "Thread Failed"),
+            Err(_) => {
+                self.processes
+                    .lock()
+                    .expect("Process Lock Poisoned")
+                    .remove(&pending);
+                format_output!("red", "!!!", "Thread Failed")
+            }
         };
         let evt = AppAction::ConsolePush(res).into_event();
         let _ = thread_tx.send(evt);
     }
 }
+
+// Writes the lines sent to the returned sender until it is dropped, which
+// closes the pipe, or until the child closes its end.
+fn write_lines(mut stdin: ChildStdin) -> mpsc::Sender<String> {
+    let (line_tx, line_rx) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in line_rx {
+            if writeln!(stdin, "{}", line)
+                .and_then(|_| stdin.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+    line_tx
+}
+
+fn read_lines<R, F>(reader: R, tx: &mpsc::Sender<events::Event>, into_action: F)
+where
+    R: Read,
+    F: Fn(String) -> AppAction,
+{
+    let mut child_out = BufReader::new(reader);
+    loop {
+        let mut buffer = String::new();
+        match child_out.read_line(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let _ = tx.send(into_action(buffer).into_event());
+            }
+        }
+    }
+}
+
+// The child stays registered until it has exited, its pipes may close
+// earlier, so it can still be signalled meanwhile.
+fn wait_exit(processes: &Arc<Mutex<HashMap<String, Process>>>, uuid: &str) -> Vec<AppAction> {
+    loop {
+        let mut running = processes.lock().expect("Process Lock Poisoned");
+        let status = match running.get_mut(uuid) {
+            Some(process) => process.try_wait(),
+            None => return end_actions(uuid, Err(String::from("Process lost")), None),
+        };
+        match status {
+            Ok(None) => {}
+            Ok(Some(status)) => {
+                return end_actions(uuid, Ok(status), running.remove(uuid));
+            }
+            Err(err) => {
+                return end_actions(uuid, Err(err.to_string()), running.remove(uuid));
+            }
+        }
+        drop(running);
+        thread::sleep(Duration::from_millis(WAIT_POLL_MILLIS));
+    }
+}
+
+// The end of `uuid`, and of the `:wait` commands waiting for it, which
+// report the same status.
+fn end_actions(
+    uuid: &str,
+    status: Result<ExitStatus, String>,
+    process: Option<Process>,
+) -> Vec<AppAction> {
+    let (signal, waiters) = match process {
+        Some(process) => (process.signal, process.waiters),
+        None => (None, Vec::new()),
+    };
+    let (success, reason) = exit_status(status, signal);
+    let mut actions = vec![AppAction::CommandEnd {
+        uuid: uuid.to_string(),
+        success,
+        reason: reason.clone(),
+    }];
+    for waiter in waiters {
+        actions.push(AppAction::CommandEnd {
+            uuid: waiter,
+            success,
+            reason: match success {
+                true => String::new(),
+                false => format!("{} ended: {}", &uuid[..uuid.len().min(8)], reason),
+            },
+        });
+    }
+    actions
+}
+
+fn exit_status(status: Result<ExitStatus, String>, signal: Option<String>) -> (bool, String) {
+    match status {
+        Ok(status) => match (status.code(), status.signal(), signal) {
+            (Some(0), _, _) => (true, String::new()),
+            (Some(code), _, _) => (false, format!("Exited with code {}", code)),
+            (None, _, Some(signal)) => (false, format!("Terminated by SIG{}", signal)),
+            (None, Some(signum), None) => (false, format!("Terminated by signal {}", signum)),
+            (None, None, None) => (false, String::from("Terminated")),
+        },
+        Err(err) => (false, err),
+    }
+}
+
+// Signals `:signal` may send, `KILL` being sent with `Child::kill`.
+fn signum(signal: &str) -> Option<libc::c_int> {
+    match signal {
+        "TERM" => Some(libc::SIGTERM),
+        "INT" => Some(libc::SIGINT),
+        _ => None,
+    }
+}
+
+impl CommandHandler {
+    pub fn running(&self) -> Vec<String> {
+        let processes = self.processes.lock().expect("Process Lock Poisoned");
+        processes.keys().cloned().collect()
+    }
+
+    /// Resolves a (possibly abbreviated) uuid to a running process.
+    pub fn find(&self, uuid: &str) -> Result<String, String> {
+        let processes = self.processes.lock().expect("Process Lock Poisoned");
+        let matches: Vec<&String> = processes.keys().filter(|id| id.starts_with(uuid)).collect();
+        match matches.len() {
+            1 => Ok(matches[0].clone()),
+            0 => Err(format!("No running process {:?}", uuid)),
+            _ => Err(format!("Ambiguous process id {:?}", uuid)),
+        }
+    }
+
+    pub fn kill(&self, uuid: &str) -> Result<String, String> {
+        let uuid = self.find(uuid)?;
+        let mut processes = self.processes.lock().expect("Process Lock Poisoned");
+        let process = processes.get_mut(&uuid).ok_or("Process already ended")?;
+        for child in process.children.iter_mut() {
+            // stages that already exited can't be killed, that's fine
+            let _ = child.kill();
+        }
+        process.signal = Some(String::from("KILL"));
+        Ok(uuid)
+    }
+
+    /// Sends `TERM` or `INT` to a running process.
+    pub fn signal(&self, uuid: &str, signal: &str) -> Result<String, String> {
+        let signum = signum(signal.trim_start_matches("SIG"))
+            .ok_or_else(|| format!("Unsupported signal {:?}", signal))?;
+        let uuid = self.find(uuid)?;
+        let mut processes = self.processes.lock().expect("Process Lock Poisoned");
+        let process = processes.get_mut(&uuid).ok_or("Process already ended")?;
+        // still spawning, `Process::start` passes it on
+        if process.children.is_empty() {
+            process.signal = Some(signal.trim_start_matches("SIG").to_string());
+            return Ok(uuid);
+        }
+        let delivered = process
+            .pids()
+            .into_iter()
+            .filter(|pid| unsafe { libc::kill(*pid as libc::pid_t, signum) } == 0)
+            .count();
+        match delivered {
+            0 => Err(format!("Failed to signal {}", uuid)),
+            _ => {
+                process.signal = Some(signal.trim_start_matches("SIG").to_string());
+                Ok(uuid)
+            }
+        }
+    }
+
+    /// Runs as its own command `uuid`, ending once `target` has ended, with
+    /// its status.
+    pub fn wait(&self, target: &str, uuid: String) -> Result<String, String> {
+        let target = self.find(target)?;
+        let mut processes = self.processes.lock().expect("Process Lock Poisoned");
+        let process = processes.get_mut(&target).ok_or("Process already ended")?;
+        process.waiters.push(uuid);
+        Ok(target)
+    }
+
+    /// Queues `line` for the stdin of a running process, without waiting
+    /// for the process to read it.
+    pub fn write_stdin(&self, uuid: &str, line: &str) -> Result<(), String> {
+        let stdin = {
+            let processes = self.processes.lock().expect("Process Lock Poisoned");
+            let process = processes.get(uuid).ok_or("Process already ended")?;
+            process.stdin.clone().ok_or("Process stdin is closed")?
+        };
+        stdin
+            .send(line.to_string())
+            .map_err(|_| String::from("Process stdin is closed"))
+    }
+
+    /// Closes the stdin of a running process, signalling end of input.
+    pub fn close_stdin(&self, uuid: &str) -> Result<(), String> {
+        let mut processes = self.processes.lock().expect("Process Lock Poisoned");
+        let process = processes.get_mut(uuid).ok_or("Process already ended")?;
+        process
+            .stdin
+            .take()
+            .map(|_| ())
+            .ok_or_else(|| String::from("Process stdin is closed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // wait(2) statuses, the exit code in the second byte
+    fn exited(code: i32) -> Result<ExitStatus, String> {
+        Ok(ExitStatus::from_raw(code << 8))
+    }
+
+    #[test]
+    fn reports_exit_statuses() {
+        assert_eq!(exit_status(exited(0), None), (true, String::new()));
+        assert_eq!(
+            exit_status(exited(2), None),
+            (false, String::from("Exited with code 2"))
+        );
+        assert_eq!(
+            exit_status(
+                Ok(ExitStatus::from_raw(libc::SIGKILL)),
+                Some(String::from("KILL"))
+            ),
+            (false, String::from("Terminated by SIGKILL"))
+        );
+    }
+
+    #[test]
+    fn ends_waiters_with_the_target_status() {
+        let mut process = Process::pending();
+        process.waiters.push(String::from("waiter"));
+        let actions = end_actions("12345678abcd", exited(1), Some(process));
+        assert_eq!(
+            actions,
+            vec![
+                AppAction::CommandEnd {
+                    uuid: String::from("12345678abcd"),
+                    success: false,
+                    reason: String::from("Exited with code 1"),
+                },
+                AppAction::CommandEnd {
+                    uuid: String::from("waiter"),
+                    success: false,
+                    reason: String::from("12345678 ended: Exited with code 1"),
+                },
+            ]
+        );
+    }
+}
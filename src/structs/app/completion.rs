@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Candidates for the word being completed in the command bar.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Completion {
+    /// The command line up to the completed word.
+    pub base: String,
+    pub candidates: Vec<String>,
+    /// Candidate currently inserted, once cycling started.
+    pub selected: Option<usize>,
+}
+
+impl Completion {
+    /// Keeps the candidates of `word`, sorted and without duplicates.
+    pub fn new(base: &str, word: &str, mut candidates: Vec<String>) -> Completion {
+        candidates.retain(|candidate| candidate.starts_with(word));
+        candidates.sort();
+        candidates.dedup();
+        Completion {
+            base: base.to_string(),
+            candidates,
+            selected: None,
+        }
+    }
+
+    /// Longest prefix shared by every candidate.
+    pub fn common_prefix(&self) -> String {
+        let mut candidates = self.candidates.iter();
+        let mut prefix = match candidates.next() {
+            Some(first) => first.clone(),
+            None => return String::new(),
+        };
+        for candidate in candidates {
+            let len = prefix
+                .char_indices()
+                .zip(candidate.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(prefix.len().min(candidate.len()), |((index, _), _)| index);
+            prefix.truncate(len);
+        }
+        prefix
+    }
+
+    /// The command line with `candidate` in place of the completed word.
+    pub fn line_with(&self, candidate: &str) -> String {
+        format!("{}{}", self.base, candidate)
+    }
+
+    pub fn select(&mut self, step: isize) -> Option<&String> {
+        let len = self.candidates.len() as isize;
+        if len == 0 {
+            return None;
+        }
+        let selected = match self.selected {
+            Some(index) => (index as isize + step).rem_euclid(len),
+            None if step < 0 => len - 1,
+            None => 0,
+        };
+        self.selected = Some(selected as usize);
+        self.candidates.get(selected as usize)
+    }
+}
+
+/// Splits the command line into the part before the last word and that word.
+pub fn split_word(line: &str) -> (&str, &str) {
+    let start = line
+        .rfind(|c: char| c.is_whitespace() || c == '|' || c == '<' || c == '>')
+        .map_or(0, |index| index + 1);
+    line.split_at(start)
+}
+
+/// Files and directories starting with `word`, directories ending in `/`.
+pub fn paths(word: &str) -> Vec<String> {
+    let (dir, prefix) = match word.rfind('/') {
+        Some(index) => word.split_at(index + 1),
+        None => ("", word),
+    };
+    let read_from = match dir {
+        "" => PathBuf::from("."),
+        dir if dir.starts_with("~/") => {
+            let home = env::var("HOME").unwrap_or_else(|_| String::from("."));
+            PathBuf::from(home).join(&dir[2..])
+        }
+        dir => PathBuf::from(dir),
+    };
+    let entries = match fs::read_dir(read_from) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            // hidden files only when asked for
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            Some(match is_dir {
+                true => format!("{}{}/", dir, name),
+                false => format!("{}{}", dir, name),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completion(word: &str, candidates: &[&str]) -> Completion {
+        let candidates = candidates.iter().map(|c| c.to_string()).collect();
+        Completion::new("exec ", word, candidates)
+    }
+
+    #[test]
+    fn keeps_matching_candidates() {
+        let completion = completion("al", &["alias", "alert", "at", "alias"]);
+        assert_eq!(completion.candidates, vec!["alert", "alias"]);
+        assert_eq!(completion.common_prefix(), "al");
+        assert_eq!(completion.line_with("alias"), "exec alias");
+    }
+
+    #[test]
+    fn finds_the_common_prefix() {
+        assert_eq!(completion("", &[]).common_prefix(), "");
+        assert_eq!(completion("", &["macro"]).common_prefix(), "macro");
+        assert_eq!(completion("", &["mark", "markets"]).common_prefix(), "mark");
+        assert_eq!(completion("", &["né", "nè"]).common_prefix(), "n");
+    }
+
+    #[test]
+    fn cycles_through_candidates() {
+        let mut completion = completion("", &["a", "b", "c"]);
+        assert_eq!(completion.select(-1).map(String::as_str), Some("c"));
+        assert_eq!(completion.select(1).map(String::as_str), Some("a"));
+        assert_eq!(completion.select(-1).map(String::as_str), Some("c"));
+        assert_eq!(completion.select(-1).map(String::as_str), Some("b"));
+
+        assert_eq!(completion("x", &["a"]).select(1), None);
+    }
+
+    #[test]
+    fn splits_the_last_word() {
+        assert_eq!(split_word("exec ls"), ("exec ", "ls"));
+        assert_eq!(split_word("exec ls|gr"), ("exec ls|", "gr"));
+        assert_eq!(split_word("exec ls >out"), ("exec ls >", "out"));
+        assert_eq!(split_word("ex"), ("", "ex"));
+        assert_eq!(split_word("exec "), ("exec ", ""));
+    }
+
+    #[test]
+    fn completes_paths() {
+        let dir = env::temp_dir().join(format!("polorust-paths-{}", ::std::process::id()));
+        fs::create_dir_all(dir.join("logs")).expect("created");
+        fs::write(dir.join("log.txt"), "").expect("written");
+        fs::write(dir.join(".hidden"), "").expect("written");
+        let base = format!("{}/", dir.display());
+
+        let mut found = paths(&format!("{}lo", base));
+        found.sort();
+        let hidden = paths(&format!("{}.h", base));
+        let all = paths(&base);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            found,
+            vec![base.clone() + "log.txt", base.clone() + "logs/"]
+        );
+        assert_eq!(hidden, vec![base.clone() + ".hidden"]);
+        assert_eq!(all.len(), 2);
+    }
+}
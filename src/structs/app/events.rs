@@ -5,6 +5,10 @@ use structs::app::AppState;
 pub enum Event {
     Render(AppState),
     Dispatch(AppAction),
+    /// Text for the terminal to put in the system clipboard.
+    Clipboard(String),
+    /// The terminal is now this many columns and rows.
+    Resize(u16, u16),
     Exit,
 }
 
@@ -55,3 +59,33 @@ pub enum Key {
     #[doc(hidden)]
     __IsNotComplete,
 }
+
+// Cargoed from termion 1.5.5
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MouseButton {
+    /// The left mouse button.
+    Left,
+    /// The right mouse button.
+    Right,
+    /// The middle mouse button.
+    Middle,
+    /// Mouse wheel is going up.
+    ///
+    /// This event is typically only used with Mouse::Press.
+    WheelUp,
+    /// Mouse wheel is going down.
+    ///
+    /// This event is typically only used with Mouse::Press.
+    WheelDown,
+}
+
+// Cargoed from termion 1.5.5, coordinates are 1-based.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Mouse {
+    /// A mouse button was pressed.
+    Press(MouseButton, u16, u16),
+    /// A mouse button was released.
+    Release(u16, u16),
+    /// A mouse button is held over the given coordinates.
+    Hold(u16, u16),
+}
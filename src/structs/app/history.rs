@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+pub const MAX_ENTRIES: usize = 1000;
+
+/// Command bar history, oldest entry first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    /// Shared between clones of the state, copied only when one changes.
+    pub entries: Arc<Vec<String>>,
+    /// Entry currently recalled with Up/Down, `None` when editing a new line.
+    pub cursor: Option<usize>,
+    /// The line being edited before recall or search started.
+    pub draft: String,
+    /// Query of an ongoing Ctrl-R search.
+    pub search: Option<String>,
+    pub search_match: Option<usize>,
+}
+
+impl History {
+    pub fn push(&mut self, line: &str) {
+        self.reset();
+        if line.trim().is_empty() || self.entries.last().map(|l| l.as_str()) == Some(line) {
+            return;
+        }
+        let entries = Arc::make_mut(&mut self.entries);
+        entries.push(line.to_string());
+        if entries.len() > MAX_ENTRIES {
+            let excess = entries.len() - MAX_ENTRIES;
+            entries.drain(..excess);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.cursor = None;
+        self.search = None;
+        self.search_match = None;
+    }
+
+    /// Steps back in time, saving `current` when leaving the new line.
+    pub fn older(&mut self, current: &str) -> Option<&String> {
+        let cursor = match self.cursor {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(cursor) => cursor.saturating_sub(1),
+        };
+        self.cursor = Some(cursor);
+        self.entries.get(cursor)
+    }
+
+    /// Steps forward in time, back to the saved line past the newest entry.
+    pub fn newer(&mut self) -> Option<&String> {
+        match self.cursor {
+            None => None,
+            Some(cursor) if cursor + 1 >= self.entries.len() => {
+                self.cursor = None;
+                Some(&self.draft)
+            }
+            Some(cursor) => {
+                self.cursor = Some(cursor + 1);
+                self.entries.get(cursor + 1)
+            }
+        }
+    }
+
+    /// Newest entry before `before` containing `query`.
+    pub fn find(&self, query: &str, before: usize) -> Option<usize> {
+        let before = before.min(self.entries.len());
+        self.entries[..before]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Vec<String>> {
+        let contents = fs::read_to_string(path)?;
+        let mut entries: Vec<String> = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from)
+            .collect();
+        if entries.len() > MAX_ENTRIES {
+            let excess = entries.len() - MAX_ENTRIES;
+            entries.drain(..excess);
+            // keep the file from growing forever
+            fs::write(path, entries.join("\n") + "\n")?;
+        }
+        Ok(entries)
+    }
+
+    pub fn append(path: &Path, line: &str) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn history(entries: &[&str]) -> History {
+        let mut history = History::default();
+        for entry in entries {
+            history.push(entry);
+        }
+        history
+    }
+
+    #[test]
+    fn skips_blank_and_repeated_lines() {
+        let history = history(&["ls", "ls", " ", "pwd", "ls"]);
+        assert_eq!(*history.entries, vec!["ls", "pwd", "ls"]);
+    }
+
+    #[test]
+    fn keeps_the_newest_entries() {
+        let mut history = History::default();
+        for index in 0..MAX_ENTRIES + 5 {
+            history.push(&index.to_string());
+        }
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        assert_eq!(history.entries[0], "5");
+    }
+
+    #[test]
+    fn shares_entries_between_clones() {
+        let mut history = history(&["ls"]);
+        let clone = history.clone();
+        assert!(Arc::ptr_eq(&history.entries, &clone.entries));
+        history.push("pwd");
+        assert_eq!(*clone.entries, vec!["ls"]);
+        assert_eq!(*history.entries, vec!["ls", "pwd"]);
+    }
+
+    #[test]
+    fn recalls_entries_and_the_draft() {
+        let mut history = history(&["one", "two"]);
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older("draft").map(String::as_str), Some("two"));
+        assert_eq!(history.older("two").map(String::as_str), Some("one"));
+        // stays on the oldest entry
+        assert_eq!(history.older("one").map(String::as_str), Some("one"));
+        assert_eq!(history.newer().map(String::as_str), Some("two"));
+        assert_eq!(history.newer().map(String::as_str), Some("draft"));
+        assert_eq!(history.cursor, None);
+
+        assert_eq!(History::default().older("draft"), None);
+    }
+
+    #[test]
+    fn finds_older_matches() {
+        let history = history(&["exec ls", "alert BTC_ETH > 1", "exec pwd"]);
+        assert_eq!(history.find("exec", 3), Some(2));
+        assert_eq!(history.find("exec", 2), Some(0));
+        assert_eq!(history.find("exec", 0), None);
+        assert_eq!(history.find("alert", 10), Some(1));
+        assert_eq!(history.find("order", 3), None);
+    }
+
+    #[test]
+    fn appends_and_loads_entries() {
+        let dir = env::temp_dir().join(format!("polorust-history-{}", ::std::process::id()));
+        let path = dir.join("history");
+        History::append(&path, "ls").expect("appended");
+        History::append(&path, "pwd").expect("appended");
+        let entries = History::load(&path);
+        let _ = fs::remove_dir_all(dir);
+        assert_eq!(entries.expect("loaded"), vec!["ls", "pwd"]);
+    }
+}
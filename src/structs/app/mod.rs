@@ -1,11 +1,34 @@
+mod alert;
+mod alias;
 mod command;
 mod command_handler;
+mod completion;
 pub mod events;
+mod history;
 mod mode;
+mod order;
+mod orderbook;
+mod palette;
+mod pipeline;
+mod quote;
+mod scheduler;
 mod setting;
 mod state;
+mod ticket;
 
+pub use self::alert::{Alert, AlertMetric, Comparison};
+pub use self::alias::Aliases;
 pub use self::command::{CmdCallback, Command};
 pub use self::command_handler::CommandHandler;
-pub use self::mode::{AppMode, ModeCategory};
+pub use self::completion::{paths, split_word, Completion};
+pub use self::history::History;
+pub use self::mode::{ModeCategory, ModeSpec, MODES};
+pub use self::order::{Order, RiskLimits, Side};
+pub use self::orderbook::Orderbook;
+pub use self::palette::{Palette, PaletteEntry};
+pub use self::pipeline::Pipeline;
+pub use self::quote::{get_quotes, Quote};
+pub use self::scheduler::{Job, Schedule, Scheduler};
+pub use self::setting::{key_name, keys_name, KeySettingItem, Settings};
 pub use self::state::AppState;
+pub use self::ticket::{Ticket, TICKET_FIELDS};
@@ -1,32 +1,81 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ModeCategory {
     Normal,
     Command,
+    /// Lines typed go to the stdin of the focused command.
+    Input,
+    /// Selecting output lines to copy.
+    Visual,
+    /// Filling in an order ticket.
+    Ticket,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub struct AppMode {
+/// What a mode does with the command bar and with keys.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModeSpec {
     pub category: ModeCategory,
-    pub symbol: String,
+    /// Used in `json_store["mode"]["category"]` and in keybindings.
+    pub name: &'static str,
+    pub symbol: &'static str,
+    /// The command bar on entering the mode.
+    pub prompt: &'static str,
+    /// Takes count prefixes, such as the `5` of `5j`.
+    pub counts: bool,
 }
 
-impl AppMode {
-    pub fn get_mode(mode_name: &str) -> AppMode {
-        match mode_name {
-            "normal" => AppMode {
-                category: ModeCategory::Normal,
-                symbol: String::from("NORM"),
-            },
-            "command" => AppMode {
-                category: ModeCategory::Command,
-                symbol: String::from("CTRL"),
-            },
-            &_ => AppMode {
-                category: ModeCategory::Command,
-                symbol: String::from("Unknown Mode"),
-            },
-        }
+pub const MODES: [ModeSpec; 5] = [
+    ModeSpec {
+        category: ModeCategory::Normal,
+        name: "normal",
+        symbol: "NORM",
+        prompt: "",
+        counts: true,
+    },
+    ModeSpec {
+        category: ModeCategory::Command,
+        name: "command",
+        symbol: "CTRL",
+        prompt: ":",
+        counts: false,
+    },
+    ModeSpec {
+        category: ModeCategory::Input,
+        name: "input",
+        symbol: "INPT",
+        prompt: ">",
+        counts: false,
+    },
+    ModeSpec {
+        category: ModeCategory::Visual,
+        name: "visual",
+        symbol: "VIS",
+        prompt: "",
+        counts: true,
+    },
+    ModeSpec {
+        category: ModeCategory::Ticket,
+        name: "ticket",
+        symbol: "TCKT",
+        prompt: "",
+        counts: false,
+    },
+];
+
+impl ModeSpec {
+    pub fn get(name: &str) -> Option<&'static ModeSpec> {
+        MODES.iter().find(|spec| spec.name == name)
+    }
+
+    /// The spec of a `SetMode` payload, or of `json_store["mode"]`.
+    pub fn of(mode: &Value) -> Option<&'static ModeSpec> {
+        mode["category"].as_str().and_then(ModeSpec::get)
+    }
+
+    pub fn names() -> Vec<&'static str> {
+        MODES.iter().map(|spec| spec.name).collect()
     }
 }
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    pub fn parse(side: &str) -> Option<Side> {
+        match side {
+            "buy" => Some(Side::Buy),
+            "sell" => Some(Side::Sell),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Side::Buy => "buy",
+            Side::Sell => "sell",
+        }
+    }
+}
+
+/// An order from a ticket or a strategy, placed with `:order`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub side: Side,
+    pub symbol: String,
+    pub amount: f64,
+    pub price: f64,
+}
+
+impl Order {
+    /// Checks the order is one that can be placed at all.
+    pub fn new(side: Side, symbol: &str, amount: f64, price: f64) -> Result<Order, String> {
+        let symbol = symbol.trim();
+        if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+            return Err(String::from("Invalid symbol"));
+        }
+        if !positive(amount) {
+            return Err(String::from("Invalid amount"));
+        }
+        if !positive(price) {
+            return Err(String::from("Invalid price"));
+        }
+        Ok(Order {
+            side,
+            symbol: symbol.to_uppercase(),
+            amount,
+            price,
+        })
+    }
+
+    /// The `order` command line placing it, so a macro named `order`
+    /// routes it like any other.
+    pub fn command(&self) -> String {
+        format!(
+            "order {} {} {} {}",
+            self.side.name(),
+            self.symbol,
+            self.amount,
+            self.price
+        )
+    }
+
+    pub fn notional(&self) -> f64 {
+        self.amount * self.price
+    }
+}
+
+fn positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+/// Limits from `settings.json` every order is checked against, unset ones
+/// don't apply.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RiskLimits {
+    #[serde(default)]
+    pub max_amount: Option<f64>,
+    /// Amount times price.
+    #[serde(default)]
+    pub max_notional: Option<f64>,
+}
+
+impl RiskLimits {
+    pub fn check(&self, order: &Order) -> Result<(), String> {
+        if let Some(max) = self.max_amount {
+            if order.amount > max {
+                return Err(format!("Amount {} over the limit of {}", order.amount, max));
+            }
+        }
+        if let Some(max) = self.max_notional {
+            if order.notional() > max {
+                return Err(format!(
+                    "Notional {} over the limit of {}",
+                    order.notional(),
+                    max
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(amount: f64, price: f64) -> Order {
+        Order::new(Side::Buy, "btc_eth", amount, price).expect("valid order")
+    }
+
+    #[test]
+    fn validates_orders() {
+        assert_eq!(order(1.5, 0.03).command(), "order buy BTC_ETH 1.5 0.03");
+        assert!(Order::new(Side::Sell, "", 1.0, 1.0).is_err());
+        assert!(Order::new(Side::Sell, "BTC ETH", 1.0, 1.0).is_err());
+        assert!(Order::new(Side::Sell, "BTC_ETH", 0.0, 1.0).is_err());
+        assert!(Order::new(Side::Sell, "BTC_ETH", 1.0, -1.0).is_err());
+        assert!(Order::new(Side::Sell, "BTC_ETH", 1.0, ::std::f64::NAN).is_err());
+    }
+
+    #[test]
+    fn checks_risk_limits() {
+        let none = RiskLimits::default();
+        assert!(none.check(&order(1e9, 1e9)).is_ok());
+
+        let limits = RiskLimits {
+            max_amount: Some(10.0),
+            max_notional: Some(1.0),
+        };
+        assert!(limits.check(&order(10.0, 0.1)).is_ok());
+        assert!(limits.check(&order(11.0, 0.01)).is_err());
+        assert!(limits.check(&order(5.0, 0.5)).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use structs::app::quote::to_f64;
+
+/// Price levels are kept as (price, size), asks ascending and bids
+/// descending, so the best level is always at index 0.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Orderbook {
+    pub symbol: String,
+    pub asks: Vec<(f64, f64)>,
+    pub bids: Vec<(f64, f64)>,
+}
+
+impl Orderbook {
+    pub fn new(symbol: &str) -> Orderbook {
+        Orderbook {
+            symbol: symbol.to_string(),
+            asks: Vec::new(),
+            bids: Vec::new(),
+        }
+    }
+
+    /// Builds a book from the "i" (initial) message of a Poloniex book channel:
+    /// `{"currencyPair": "BTC_ETH", "orderBook": [{asks}, {bids}]}`
+    pub fn from_snapshot(snapshot: &Value) -> Orderbook {
+        let symbol = snapshot["currencyPair"].as_str().unwrap_or("");
+        let mut book = Orderbook::new(symbol);
+        if let Some(asks) = snapshot["orderBook"][0].as_object() {
+            for (price, size) in asks.iter() {
+                book.apply(false, price.parse::<f64>().unwrap_or(0.0), to_f64(size));
+            }
+        }
+        if let Some(bids) = snapshot["orderBook"][1].as_object() {
+            for (price, size) in bids.iter() {
+                book.apply(true, price.parse::<f64>().unwrap_or(0.0), to_f64(size));
+            }
+        }
+        book
+    }
+
+    /// Sets the size of a level, a size of zero removes it.
+    pub fn apply(&mut self, is_bid: bool, price: f64, size: f64) {
+        let levels = match is_bid {
+            true => &mut self.bids,
+            false => &mut self.asks,
+        };
+        let position = levels.iter().position(|&(p, _)| match is_bid {
+            true => p <= price,
+            false => p >= price,
+        });
+        match position {
+            Some(index) if levels[index].0 == price => {
+                if size > 0.0 {
+                    levels[index].1 = size;
+                } else {
+                    levels.remove(index);
+                }
+            }
+            Some(index) if size > 0.0 => levels.insert(index, (price, size)),
+            None if size > 0.0 => levels.push((price, size)),
+            _ => {}
+        }
+    }
+
+    pub fn best_ask(&self) -> Option<(f64, f64)> {
+        self.asks.first().cloned()
+    }
+
+    pub fn best_bid(&self) -> Option<(f64, f64)> {
+        self.bids.first().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn builds_books_from_snapshots() {
+        let snapshot = json!({
+            "currencyPair": "BTC_ETH",
+            "orderBook": [
+                {"0.0330": "2.0", "0.0320": "1.0", "0.0340": "3.0"},
+                {"0.0310": "1.5", "0.0300": "4.0"}
+            ]
+        });
+        let book = Orderbook::from_snapshot(&snapshot);
+        assert_eq!(book.symbol, "BTC_ETH");
+        assert_eq!(book.asks, vec![(0.032, 1.0), (0.033, 2.0), (0.034, 3.0)]);
+        assert_eq!(book.bids, vec![(0.031, 1.5), (0.03, 4.0)]);
+        assert_eq!(book.best_ask(), Some((0.032, 1.0)));
+        assert_eq!(book.best_bid(), Some((0.031, 1.5)));
+    }
+
+    #[test]
+    fn applies_level_changes() {
+        let mut book = Orderbook::new("BTC_ETH");
+        book.apply(true, 1.0, 2.0);
+        book.apply(true, 3.0, 1.0);
+        book.apply(true, 2.0, 1.0);
+        assert_eq!(book.bids, vec![(3.0, 1.0), (2.0, 1.0), (1.0, 2.0)]);
+        book.apply(true, 2.0, 5.0);
+        assert_eq!(book.bids[1], (2.0, 5.0));
+
+        // a size of zero removes the level, or adds nothing
+        book.apply(true, 3.0, 0.0);
+        book.apply(true, 4.0, 0.0);
+        assert_eq!(book.bids, vec![(2.0, 5.0), (1.0, 2.0)]);
+        assert_eq!(book.best_bid(), Some((2.0, 5.0)));
+        assert_eq!(book.best_ask(), None);
+    }
+}
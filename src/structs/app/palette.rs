@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub detail: String,
+    /// Command put in the command bar when picked, none for keybindings.
+    pub command: Option<String>,
+}
+
+/// Ctrl-P popup listing commands and keybindings, filtered as you type.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    pub entries: Vec<PaletteEntry>,
+    pub query: String,
+    /// Index into `matches()`.
+    pub selected: usize,
+}
+
+impl Palette {
+    pub fn new(entries: Vec<PaletteEntry>) -> Palette {
+        Palette {
+            entries,
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Entries matching the query, best first.
+    pub fn matches(&self) -> Vec<&PaletteEntry> {
+        let mut scored: Vec<(i64, &PaletteEntry)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let text = format!("{} {}", entry.label, entry.detail);
+                fuzzy_score(&self.query, &text).map(|score| (score, entry))
+            })
+            .collect();
+        // stable, so equal scores keep the registry order
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    pub fn selected_entry(&self) -> Option<&PaletteEntry> {
+        self.matches().get(self.selected).cloned()
+    }
+
+    pub fn select(&mut self, step: isize) {
+        let len = self.matches().len() as isize;
+        if len > 0 {
+            self.selected = (self.selected as isize + step).rem_euclid(len) as usize;
+        }
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.selected = 0;
+    }
+}
+
+/// Whether the chars of `query` appear in order in `text`, ignoring case,
+/// scored higher for matches that are consecutive or start words.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous.map_or(false, |previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    // shorter texts first among equals
+    Some(score * 100 - text.len() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> Palette {
+        let entry = |label: &str, detail: &str| PaletteEntry {
+            label: label.to_string(),
+            detail: detail.to_string(),
+            command: Some(label.to_string()),
+        };
+        Palette::new(vec![
+            entry("tabclose", "Close a tab"),
+            entry("alert", "Set an alert"),
+            entry("alias", "Define an alias"),
+        ])
+    }
+
+    fn labels(palette: &Palette) -> Vec<&str> {
+        palette
+            .matches()
+            .iter()
+            .map(|entry| entry.label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn scores_consecutive_and_word_start_matches_higher() {
+        let consecutive = fuzzy_score("al", "alert").expect("match");
+        let word_starts = fuzzy_score("al", "a_l").expect("match");
+        let scattered = fuzzy_score("al", "xaxl").expect("match");
+        assert!(consecutive > word_starts);
+        assert!(word_starts > scattered);
+        assert_eq!(fuzzy_score("A L", "alert"), Some(consecutive));
+    }
+
+    #[test]
+    fn needs_every_char_in_order() {
+        assert_eq!(fuzzy_score("xyz", "alert"), None);
+        assert_eq!(fuzzy_score("tl", "alert"), None);
+        assert_eq!(fuzzy_score("alerts", "alert"), None);
+    }
+
+    #[test]
+    fn orders_matches() {
+        let mut palette = palette();
+        // everything matches an empty query, shortest first
+        assert_eq!(labels(&palette), vec!["alert", "tabclose", "alias"]);
+        palette.set_query(String::from("al"));
+        assert_eq!(labels(&palette), vec!["alert", "alias", "tabclose"]);
+        palette.set_query(String::from("xyz"));
+        assert!(palette.matches().is_empty());
+        assert_eq!(palette.selected_entry(), None);
+    }
+
+    #[test]
+    fn cycles_the_selection() {
+        let mut palette = palette();
+        palette.select(-1);
+        assert_eq!(
+            palette.selected_entry().map(|e| e.label.as_str()),
+            Some("alias")
+        );
+        palette.select(1);
+        assert_eq!(palette.selected, 0);
+        palette.select(1);
+        palette.set_query(String::from("al"));
+        assert_eq!(palette.selected, 0);
+        palette.set_query(String::from("xyz"));
+        palette.select(1);
+        assert_eq!(palette.selected, 0);
+    }
+}
@@ -0,0 +1,167 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::process::{Child, Command, Stdio};
+use utils::shell::Token;
+
+/// `cmd [args] [< file] [| cmd [args]]... [> file | >> file]`, run by wiring
+/// `std::process::Command`s together rather than through a shell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pipeline {
+    pub stages: Vec<Vec<String>>,
+    pub stdin: Option<String>,
+    /// Target file and whether to append to it.
+    pub stdout: Option<(String, bool)>,
+}
+
+impl Pipeline {
+    pub fn parse(tokens: &[Token]) -> Result<Pipeline, String> {
+        let mut pipeline = Pipeline {
+            stages: Vec::new(),
+            stdin: None,
+            stdout: None,
+        };
+        let mut stage: Vec<String> = Vec::new();
+        let mut tokens = tokens.iter();
+        while let Some(token) = tokens.next() {
+            if pipeline.stdout.is_some() {
+                return Err(String::from("Output redirection must come last"));
+            }
+            match token {
+                Token::Word(word) => stage.push(word.clone()),
+                Token::Pipe => {
+                    if stage.is_empty() {
+                        return Err(String::from("Empty command in pipeline"));
+                    }
+                    pipeline.stages.push(stage);
+                    stage = Vec::new();
+                }
+                Token::Read | Token::Write | Token::Append => {
+                    let path = match tokens.next() {
+                        Some(Token::Word(path)) => path.clone(),
+                        _ => return Err(format!("Missing file after {}", token.as_str())),
+                    };
+                    match token {
+                        Token::Read if pipeline.stages.is_empty() && pipeline.stdin.is_none() => {
+                            pipeline.stdin = Some(path)
+                        }
+                        Token::Read => {
+                            return Err(String::from(
+                                "Input redirection only applies to the first command",
+                            ))
+                        }
+                        _ => pipeline.stdout = Some((path, *token == Token::Append)),
+                    }
+                }
+            }
+        }
+        if stage.is_empty() {
+            return Err(String::from("Empty command in pipeline"));
+        }
+        pipeline.stages.push(stage);
+        Ok(pipeline)
+    }
+
+    /// Spawns every stage, each one's stdout feeding the next one's stdin.
+    /// Stderr of all stages is piped, as is the last stdout unless it is
+    /// redirected and the first stdin unless it is read from a file.
+    pub fn spawn(&self) -> io::Result<Vec<Child>> {
+        let mut children: Vec<Child> = Vec::new();
+        for (index, stage) in self.stages.iter().enumerate() {
+            match self.spawn_stage(index, stage, children.last_mut()) {
+                Ok(child) => children.push(child),
+                Err(err) => {
+                    for mut child in children {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(children)
+    }
+
+    fn spawn_stage(
+        &self,
+        index: usize,
+        stage: &[String],
+        prev: Option<&mut Child>,
+    ) -> io::Result<Child> {
+        let stdin = match (prev, &self.stdin) {
+            (Some(prev), _) => match prev.stdout.take() {
+                Some(out) => Stdio::from(out),
+                None => Stdio::null(),
+            },
+            (None, Some(path)) => Stdio::from(File::open(path)?),
+            (None, None) => Stdio::piped(),
+        };
+        let stdout = match (index + 1 == self.stages.len(), &self.stdout) {
+            (true, Some((path, append))) => Stdio::from(
+                OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .append(*append)
+                    .truncate(!*append)
+                    .open(path)?,
+            ),
+            _ => Stdio::piped(),
+        };
+        Command::new(&stage[0])
+            .args(&stage[1..])
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(Stdio::piped())
+            .spawn()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::shell::tokenize;
+
+    fn parse(line: &str) -> Result<Pipeline, String> {
+        Pipeline::parse(&tokenize(line).expect("tokenized"))
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_pipelines() {
+        let pipeline = parse("grep -v x < in.txt | sort | uniq -c >> out.txt").expect("parsed");
+        assert_eq!(
+            pipeline.stages,
+            vec![
+                words(&["grep", "-v", "x"]),
+                words(&["sort"]),
+                words(&["uniq", "-c"]),
+            ]
+        );
+        assert_eq!(pipeline.stdin, Some(String::from("in.txt")));
+        assert_eq!(pipeline.stdout, Some((String::from("out.txt"), true)));
+
+        let pipeline = parse("ls > out.txt").expect("parsed");
+        assert_eq!(pipeline.stages, vec![words(&["ls"])]);
+        assert_eq!(pipeline.stdin, None);
+        assert_eq!(pipeline.stdout, Some((String::from("out.txt"), false)));
+    }
+
+    #[test]
+    fn rejects_empty_commands() {
+        assert!(parse("").is_err());
+        assert!(parse("| sort").is_err());
+        assert!(parse("ls |").is_err());
+        assert!(parse("ls | | sort").is_err());
+    }
+
+    #[test]
+    fn rejects_misplaced_redirections() {
+        assert!(parse("ls >").is_err());
+        assert!(parse("sort <").is_err());
+        assert!(parse("ls > out.txt | sort").is_err());
+        assert!(parse("ls | sort < in.txt").is_err());
+        assert!(parse("sort < a < b").is_err());
+    }
+}
@@ -1,14 +1,62 @@
-use cpython::{PyDict, Python, ToPyObject};
+use cpython::{exc, FromPyObject, PyDict, PyErr, PyObject, PyResult, Python, ToPyObject};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use structs::app::{AppState, Orderbook};
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Quote {
     pub symbol: String,
-    pub bid_price: i32,
-    pub ask_price: i32,
-    pub bid_size: i32,
-    pub ask_size: i32,
+    pub bid_price: f64,
+    pub ask_price: f64,
+    pub bid_size: f64,
+    pub ask_size: f64,
     pub timestamp: i64,
 }
 
+impl Quote {
+    /// Builds a quote from the top of an order book.
+    pub fn from_book(book: &Orderbook, timestamp: i64) -> Quote {
+        let (bid_price, bid_size) = book.best_bid().unwrap_or((0.0, 0.0));
+        let (ask_price, ask_size) = book.best_ask().unwrap_or((0.0, 0.0));
+        Quote {
+            symbol: book.symbol.clone(),
+            bid_price,
+            ask_price,
+            bid_size,
+            ask_size,
+            timestamp,
+        }
+    }
+
+    /// `None` until both sides of the book have orders, an empty side
+    /// being priced at zero.
+    pub fn mid(&self) -> Option<f64> {
+        match self.bid_price > 0.0 && self.ask_price > 0.0 {
+            true => Some((self.bid_price + self.ask_price) / 2.0),
+            false => None,
+        }
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        self.mid().map(|_| self.ask_price - self.bid_price)
+    }
+
+    /// Spread as a percentage of the mid price.
+    pub fn spread_pct(&self) -> Option<f64> {
+        self.mid()
+            .map(|mid| (self.ask_price - self.bid_price) / mid * 100.0)
+    }
+}
+
+/// Exchange payloads send numbers as strings, accept both.
+pub fn to_f64(value: &Value) -> f64 {
+    match value {
+        Value::String(s) => s.parse::<f64>().unwrap_or(0.0),
+        Value::Number(n) => n.as_f64().unwrap_or(0.0),
+        _ => 0.0,
+    }
+}
+
 impl ToPyObject for Quote {
     type ObjectType = PyDict;
 
@@ -24,25 +72,80 @@ impl ToPyObject for Quote {
     }
 }
 
-pub fn get_quotes() -> Vec<Quote> {
-    let quotes = vec![
-        Quote {
-            symbol: String::from("btc-usd"),
-            bid_price: 1000,
-            ask_price: 1100,
-            bid_size: 100,
-            ask_size: 100,
-            timestamp: 13213123,
-        },
-        Quote {
-            symbol: String::from("btc-usd"),
-            bid_price: 1000,
-            ask_price: 1100,
-            bid_size: 100,
-            ask_size: 100,
-            timestamp: 13213123,
-        },
-    ];
-
-    return quotes;
+impl<'s> FromPyObject<'s> for Quote {
+    fn extract(py: Python, obj: &'s PyObject) -> PyResult<Quote> {
+        let dict = obj.cast_as::<PyDict>(py)?;
+        Ok(Quote {
+            symbol: get_item(py, dict, "symbol")?,
+            bid_price: get_item(py, dict, "bid_price")?,
+            ask_price: get_item(py, dict, "ask_price")?,
+            bid_size: get_item_or(py, dict, "bid_size", 0.0)?,
+            ask_size: get_item_or(py, dict, "ask_size", 0.0)?,
+            timestamp: get_item_or(py, dict, "timestamp", 0)?,
+        })
+    }
+}
+
+fn get_item<T>(py: Python, dict: &PyDict, key: &str) -> PyResult<T>
+where
+    T: for<'a> FromPyObject<'a>,
+{
+    match dict.get_item(py, key) {
+        Some(value) => value.extract(py),
+        None => Err(PyErr::new::<exc::KeyError, _>(py, key)),
+    }
+}
+
+fn get_item_or<T>(py: Python, dict: &PyDict, key: &str, default: T) -> PyResult<T>
+where
+    T: for<'a> FromPyObject<'a>,
+{
+    match dict.get_item(py, key) {
+        Some(value) => value.extract(py),
+        None => Ok(default),
+    }
+}
+
+pub fn get_quotes(state: &AppState) -> Vec<Quote> {
+    let mut quotes: Vec<Quote> = state.quotes.values().cloned().collect();
+    quotes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    quotes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_the_top_of_the_book() {
+        let mut book = Orderbook::new("BTC_ETH");
+        book.apply(true, 1.0, 2.0);
+        book.apply(true, 0.5, 1.0);
+        book.apply(false, 3.0, 4.0);
+        let quote = Quote::from_book(&book, 42);
+        assert_eq!((quote.bid_price, quote.bid_size), (1.0, 2.0));
+        assert_eq!((quote.ask_price, quote.ask_size), (3.0, 4.0));
+        assert_eq!(quote.mid(), Some(2.0));
+        assert_eq!(quote.spread(), Some(2.0));
+        assert_eq!(quote.spread_pct(), Some(100.0));
+    }
+
+    #[test]
+    fn has_no_mid_with_an_empty_side() {
+        let mut book = Orderbook::new("BTC_ETH");
+        book.apply(true, 1.0, 2.0);
+        let quote = Quote::from_book(&book, 0);
+        assert_eq!(quote.ask_price, 0.0);
+        assert_eq!(quote.mid(), None);
+        assert_eq!(quote.spread(), None);
+        assert_eq!(quote.spread_pct(), None);
+    }
+
+    #[test]
+    fn reads_numbers_and_strings() {
+        assert_eq!(to_f64(&Value::from("0.5")), 0.5);
+        assert_eq!(to_f64(&Value::from(2)), 2.0);
+        assert_eq!(to_f64(&Value::from("x")), 0.0);
+        assert_eq!(to_f64(&Value::Null), 0.0);
+    }
 }
@@ -0,0 +1,241 @@
+use actions::AppAction;
+use chrono::{Duration as ChronoDuration, Local, TimeZone};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use structs::app::events::Event;
+use utils::market::now_millis;
+use uuid::Uuid;
+
+const TICK_MILLIS: u64 = 250;
+// Next runs are i64 milliseconds.
+const MAX_EVERY_SECS: u64 = i64::max_value() as u64 / 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Schedule {
+    /// Recurring, every n seconds.
+    Every(u64),
+    /// Once, at the next occurrence of hour:minute local time.
+    At(u32, u32),
+}
+
+impl Schedule {
+    /// Parses `30s`, `5m`, `2h`, `1d` or a plain number of seconds.
+    pub fn parse_every(interval: &str) -> Result<Schedule, String> {
+        let (number, unit) = match interval.find(|c: char| !c.is_ascii_digit()) {
+            Some(index) => interval.split_at(index),
+            None => (interval, "s"),
+        };
+        let multiplier = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            &_ => return Err(format!("Unknown interval unit {:?}", unit)),
+        };
+        let n = match number.parse::<u64>() {
+            Ok(n) if n > 0 => n,
+            _ => return Err(format!("Invalid interval {:?}", interval)),
+        };
+        match n.checked_mul(multiplier) {
+            Some(secs) if secs <= MAX_EVERY_SECS => Ok(Schedule::Every(secs)),
+            _ => Err(format!("Interval {:?} is too long", interval)),
+        }
+    }
+
+    /// Parses `HH:MM`.
+    pub fn parse_at(time: &str) -> Result<Schedule, String> {
+        let parts: Vec<&str> = time.split(':').collect();
+        let err = || format!("Invalid time {:?}, expected HH:MM", time);
+        if parts.len() != 2 {
+            return Err(err());
+        }
+        let hour = parts[0].parse::<u32>().map_err(|_| err())?;
+        let minute = parts[1].parse::<u32>().map_err(|_| err())?;
+        match hour < 24 && minute < 60 {
+            true => Ok(Schedule::At(hour, minute)),
+            false => Err(err()),
+        }
+    }
+
+    fn next_run(&self, now: i64) -> i64 {
+        match *self {
+            Schedule::Every(secs) => now.saturating_add(secs as i64 * 1000),
+            Schedule::At(hour, minute) => {
+                let today = Local.timestamp_millis(now).date().naive_local();
+                // a time skipped by a DST change runs the day after, a
+                // repeated one at its first occurrence
+                (0..3)
+                    .filter_map(|days| {
+                        let date = today + ChronoDuration::days(days);
+                        let time = date.and_hms_opt(hour, minute, 0)?;
+                        Local.from_local_datetime(&time).earliest()
+                    })
+                    .map(|target| target.timestamp_millis())
+                    .find(|target| *target > now)
+                    .unwrap_or(i64::max_value())
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match *self {
+            Schedule::Every(secs) => format!("every {}s", secs),
+            Schedule::At(hour, minute) => format!("at {:02}:{:02}", hour, minute),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub id: usize,
+    pub schedule: Schedule,
+    pub cmd_str: String,
+    pub next_run: i64,
+}
+
+impl Job {
+    pub fn describe(&self) -> String {
+        let next = match Local.timestamp_millis_opt(self.next_run).single() {
+            Some(next) => next.format("%H:%M:%S").to_string(),
+            None => String::from("never"),
+        };
+        format!(
+            "{} :{} (next {})",
+            self.schedule.describe(),
+            self.cmd_str,
+            next
+        )
+    }
+}
+
+/// Owns the scheduled jobs and a timer thread that enqueues their commands
+/// through `CommandEnqueue`, just as if they were typed in the command bar.
+pub struct Scheduler {
+    jobs: Arc<Mutex<Vec<Job>>>,
+    next_id: Mutex<usize>,
+}
+
+impl Scheduler {
+    pub fn new(tx: Sender<Event>) -> Scheduler {
+        let jobs: Arc<Mutex<Vec<Job>>> = Arc::new(Mutex::new(Vec::new()));
+        let thread_jobs = jobs.clone();
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_millis(TICK_MILLIS));
+            let now = now_millis();
+            let mut jobs = thread_jobs.lock().expect("Scheduler Lock Poisoned");
+            let mut done: Vec<usize> = Vec::new();
+            for job in jobs.iter_mut().filter(|job| job.next_run <= now) {
+                let evt = AppAction::CommandEnqueue {
+                    uuid: Uuid::new_v4().to_string(),
+                    cmd_str: job.cmd_str.clone(),
+                }
+                .into_event();
+                let _ = tx.send(evt);
+                match job.schedule {
+                    Schedule::Every(_) => job.next_run = job.schedule.next_run(now),
+                    Schedule::At(_, _) => done.push(job.id),
+                }
+            }
+            jobs.retain(|job| !done.contains(&job.id));
+        });
+        Scheduler {
+            jobs,
+            next_id: Mutex::new(0),
+        }
+    }
+
+    pub fn add(&self, schedule: Schedule, cmd_str: String) -> Job {
+        let mut next_id = self.next_id.lock().expect("Scheduler Lock Poisoned");
+        *next_id += 1;
+        let job = Job {
+            id: *next_id,
+            next_run: schedule.next_run(now_millis()),
+            schedule,
+            cmd_str,
+        };
+        self.jobs
+            .lock()
+            .expect("Scheduler Lock Poisoned")
+            .push(job.clone());
+        job
+    }
+
+    pub fn remove(&self, id: usize) -> Option<Job> {
+        let mut jobs = self.jobs.lock().expect("Scheduler Lock Poisoned");
+        jobs.iter()
+            .position(|job| job.id == id)
+            .map(|index| jobs.remove(index))
+    }
+
+    pub fn jobs(&self) -> Vec<Job> {
+        self.jobs.lock().expect("Scheduler Lock Poisoned").clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Timelike};
+
+    fn local_millis(hour: u32, minute: u32) -> i64 {
+        let time = NaiveDate::from_ymd(2024, 1, 15).and_hms(hour, minute, 0);
+        Local
+            .from_local_datetime(&time)
+            .earliest()
+            .expect("a local time")
+            .timestamp_millis()
+    }
+
+    #[test]
+    fn parses_intervals() {
+        assert_eq!(Schedule::parse_every("30"), Ok(Schedule::Every(30)));
+        assert_eq!(Schedule::parse_every("30s"), Ok(Schedule::Every(30)));
+        assert_eq!(Schedule::parse_every("5m"), Ok(Schedule::Every(300)));
+        assert_eq!(Schedule::parse_every("2h"), Ok(Schedule::Every(7200)));
+        assert_eq!(Schedule::parse_every("1d"), Ok(Schedule::Every(86400)));
+        assert!(Schedule::parse_every("0s").is_err());
+        assert!(Schedule::parse_every("5w").is_err());
+        assert!(Schedule::parse_every("m").is_err());
+    }
+
+    #[test]
+    fn rejects_intervals_that_overflow() {
+        assert!(Schedule::parse_every("18446744073709551615d").is_err());
+        assert!(Schedule::parse_every("9223372036854775s").is_ok());
+        assert!(Schedule::parse_every("9223372036854776s").is_err());
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(Schedule::parse_at("09:30"), Ok(Schedule::At(9, 30)));
+        assert_eq!(Schedule::parse_at("23:59"), Ok(Schedule::At(23, 59)));
+        assert!(Schedule::parse_at("24:00").is_err());
+        assert!(Schedule::parse_at("12:60").is_err());
+        assert!(Schedule::parse_at("1230").is_err());
+    }
+
+    #[test]
+    fn runs_every_interval() {
+        assert_eq!(Schedule::Every(5).next_run(1000), 6000);
+        assert_eq!(
+            Schedule::Every(MAX_EVERY_SECS).next_run(1000),
+            i64::max_value()
+        );
+    }
+
+    #[test]
+    fn runs_at_the_next_occurrence() {
+        let now = local_millis(12, 0);
+        assert_eq!(Schedule::At(13, 30).next_run(now), local_millis(13, 30));
+
+        let tomorrow = Schedule::At(11, 0).next_run(now);
+        assert!(tomorrow > now);
+        let at = Local.timestamp_millis(tomorrow);
+        assert_eq!((at.hour(), at.minute()), (11, 0));
+
+        let same_minute = Schedule::At(12, 0).next_run(now);
+        assert!(same_minute > now);
+    }
+}
@@ -1,9 +1,395 @@
 use actions::AppAction;
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
 use structs::app::events::Key as SerializableKey;
+use structs::app::{ModeSpec, RiskLimits};
+use structs::ui::DEFAULT_SCROLLBACK;
+use utils::market::{DEFAULT_FEED, DEFAULT_MARKETS};
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+const DEFAULT_LEADER: SerializableKey = SerializableKey::Char('\\');
+const DEFAULT_KEY_TIMEOUT_MS: u64 = 1000;
+const DEFAULT_FPS: u32 = 30;
+
+/// A key, or a sequence of keys, bound to an action or to a command line
+/// run as if typed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct KeySettingItem {
-    key: SerializableKey,
-    action: AppAction,
+    pub key: SerializableKey,
+    /// Keys pressed after `key`, as in `gt`.
+    #[serde(default)]
+    pub chord: Vec<SerializableKey>,
+    /// The sequence starts with the leader key, followed by `key`.
+    #[serde(default)]
+    pub leader: bool,
+    /// Mode category the binding applies in, all of them when unset.
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub action: Option<AppAction>,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl KeySettingItem {
+    pub fn action(mode: &str, key: SerializableKey, action: AppAction, description: &str) -> Self {
+        KeySettingItem {
+            key,
+            chord: Vec::new(),
+            leader: false,
+            mode: Some(mode.to_string()),
+            action: Some(action),
+            command: None,
+            description: Some(description.to_string()),
+        }
+    }
+
+    pub fn command(mode: &str, key: SerializableKey, command: &str, description: &str) -> Self {
+        KeySettingItem {
+            key,
+            chord: Vec::new(),
+            leader: false,
+            mode: Some(mode.to_string()),
+            action: None,
+            command: Some(command.to_string()),
+            description: Some(description.to_string()),
+        }
+    }
+
+    pub fn followed_by(mut self, chord: &[SerializableKey]) -> Self {
+        self.chord = chord.to_vec();
+        self
+    }
+
+    pub fn keys(&self) -> Vec<SerializableKey> {
+        let mut keys = vec![self.key];
+        keys.extend(self.chord.iter().cloned());
+        keys
+    }
+
+    /// Bound to exactly `keys` in `category`.
+    pub fn applies(&self, category: &str, keys: &[SerializableKey]) -> bool {
+        self.in_mode(category) && self.keys() == keys
+    }
+
+    /// Bound to a longer sequence starting with `keys` in `category`.
+    pub fn extends(&self, category: &str, keys: &[SerializableKey]) -> bool {
+        let own = self.keys();
+        self.in_mode(category) && own.len() > keys.len() && own.starts_with(keys)
+    }
+
+    fn in_mode(&self, category: &str) -> bool {
+        self.mode.as_ref().map_or(true, |mode| mode == category)
+    }
+
+    // `leader` spelled out, so lookups only compare keys
+    fn with_leader(mut self, leader: SerializableKey) -> Self {
+        if self.leader {
+            self.chord.insert(0, self.key);
+            self.key = leader;
+            self.leader = false;
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let key = keys_name(&self.keys());
+        if let Some(ref mode) = self.mode {
+            if ModeSpec::get(mode).is_none() {
+                return Err(format!("{}: unknown mode {:?}", key, mode));
+            }
+        }
+        match (&self.action, &self.command) {
+            (Some(_), Some(_)) => Err(format!("{}: set either action or command", key)),
+            (None, None) => Err(format!("{}: missing action or command", key)),
+            (Some(AppAction::SetMode(mode)), None) => match ModeSpec::of(mode) {
+                Some(_) => Ok(()),
+                None => Err(format!("{}: unknown mode {:?}", key, mode["category"])),
+            },
+            (Some(AppAction::Keyboard(_)), None) => {
+                Err(format!("{}: Keyboard actions can't be bound", key))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.description, &self.command, &self.action) {
+            (Some(description), _, _) => description.clone(),
+            (None, Some(command), _) => format!(":{}", command),
+            (None, None, Some(action)) => format!("{:?}", action),
+            (None, None, None) => String::new(),
+        }
+    }
+}
+
+/// Contents of `settings.json`, every part optional.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub keys: Vec<KeySettingItem>,
+    /// Key standing for `leader` in bindings, `\` by default.
+    #[serde(default)]
+    pub leader: Option<SerializableKey>,
+    /// Milliseconds a partial sequence waits for its next key.
+    #[serde(default)]
+    pub key_timeout: Option<u64>,
+    /// Frames drawn per second at most, states in between are skipped.
+    #[serde(default)]
+    pub fps: Option<u32>,
+    /// Lines kept by the console and by each command's output.
+    #[serde(default)]
+    pub scrollback: Option<usize>,
+    /// File the lines past the scrollback are appended to, under
+    /// `~/.cryptocmd` unless absolute. Dropped for good when unset.
+    #[serde(default)]
+    pub spill: Option<String>,
+    /// Websocket of the market feed, Poloniex's by default.
+    #[serde(default)]
+    pub feed: Option<String>,
+    /// Order books subscribed to, `BTC_ETH` by default. None at all leaves
+    /// the feed off.
+    #[serde(default)]
+    pub markets: Option<Vec<String>>,
+    /// Limits orders from tickets and strategies are checked against.
+    #[serde(default)]
+    pub risk: RiskLimits,
+}
+
+impl Settings {
+    /// Missing file means defaults, anything else wrong is an error.
+    pub fn load(path: &Path) -> Result<Settings, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn feed(&self) -> &str {
+        self.feed
+            .as_ref()
+            .map_or(DEFAULT_FEED, |feed| feed.as_str())
+    }
+
+    pub fn markets(&self) -> Vec<String> {
+        match self.markets {
+            Some(ref markets) => markets.clone(),
+            None => DEFAULT_MARKETS
+                .iter()
+                .map(|market| market.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn leader(&self) -> SerializableKey {
+        self.leader.unwrap_or(DEFAULT_LEADER)
+    }
+
+    pub fn key_timeout(&self) -> Duration {
+        Duration::from_millis(self.key_timeout.unwrap_or(DEFAULT_KEY_TIMEOUT_MS))
+    }
+
+    pub fn scrollback(&self) -> usize {
+        self.scrollback.unwrap_or(DEFAULT_SCROLLBACK).max(1)
+    }
+
+    /// Least time between two frames.
+    pub fn frame_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.fps.unwrap_or(DEFAULT_FPS).max(1)
+    }
+
+    /// Valid user bindings ahead of `defaults`, so they win, and the
+    /// errors of the invalid ones.
+    pub fn keymap(&self, defaults: Vec<KeySettingItem>) -> (Vec<KeySettingItem>, Vec<String>) {
+        let leader = self.leader();
+        let mut errors = Vec::new();
+        let mut keymap: Vec<KeySettingItem> = Vec::new();
+        for item in &self.keys {
+            match item.validate() {
+                Ok(()) => keymap.push(item.clone().with_leader(leader)),
+                Err(err) => errors.push(err),
+            }
+        }
+        keymap.extend(defaults.into_iter().map(|item| item.with_leader(leader)));
+        (keymap, errors)
+    }
+}
+
+pub fn key_name(key: &SerializableKey) -> String {
+    match key {
+        SerializableKey::Char('\n') => String::from("Enter"),
+        SerializableKey::Char('\t') => String::from("Tab"),
+        SerializableKey::Char(' ') => String::from("Space"),
+        SerializableKey::Char(c) => c.to_string(),
+        SerializableKey::Alt(c) => format!("Alt-{}", c),
+        SerializableKey::Ctrl(c) => format!("Ctrl-{}", c.to_uppercase()),
+        SerializableKey::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    }
+}
+
+/// `gt`, `Ctrl-W j`: single characters run together, longer names spaced.
+pub fn keys_name(keys: &[SerializableKey]) -> String {
+    let mut name = String::new();
+    let mut spaced = false;
+    for key in keys {
+        let part = key_name(key);
+        let long = part.chars().count() > 1;
+        if !name.is_empty() && (long || spaced) {
+            name.push(' ');
+        }
+        name.push_str(&part);
+        spaced = long;
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn temp_file(name: &str, contents: &str) -> ::std::path::PathBuf {
+        let path = env::temp_dir().join(format!("polorust-{}-{}", name, ::std::process::id()));
+        fs::write(&path, contents).expect("written");
+        path
+    }
+
+    fn command(mode: &str, key: SerializableKey, command: &str) -> KeySettingItem {
+        KeySettingItem {
+            key,
+            mode: Some(mode.to_string()),
+            action: None,
+            command: Some(command.to_string()),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn loads_defaults_only_when_missing() {
+        let missing = env::temp_dir().join("polorust-no-such-settings.json");
+        let settings = Settings::load(&missing).expect("defaults");
+        assert!(settings.keys.is_empty());
+        assert_eq!(settings.scrollback(), DEFAULT_SCROLLBACK);
+        assert_eq!(settings.feed(), DEFAULT_FEED);
+        assert_eq!(settings.markets(), vec![String::from("BTC_ETH")]);
+        assert_eq!(
+            settings.frame_interval(),
+            Duration::from_secs(1) / DEFAULT_FPS
+        );
+
+        // a directory can't be read as a file
+        assert!(Settings::load(&env::temp_dir()).is_err());
+
+        let broken = temp_file("broken.json", "{\"feed\": ");
+        assert!(Settings::load(&broken).is_err());
+        let _ = fs::remove_file(broken);
+    }
+
+    #[test]
+    fn loads_settings() {
+        let path = temp_file(
+            "settings.json",
+            "{\"feed\": \"wss://localhost\", \"markets\": [], \"fps\": 10, \"scrollback\": 0}",
+        );
+        let settings = Settings::load(&path).expect("settings");
+        let _ = fs::remove_file(path);
+        assert_eq!(settings.feed(), "wss://localhost");
+        assert!(settings.markets().is_empty());
+        assert_eq!(settings.frame_interval(), Duration::from_millis(100));
+        assert_eq!(settings.scrollback(), 1);
+        assert_eq!(settings.risk, RiskLimits::default());
+    }
+
+    #[test]
+    fn user_bindings_come_first() {
+        let settings = Settings {
+            keys: vec![
+                command("normal", SerializableKey::Ctrl('p'), "help"),
+                command("nowhere", SerializableKey::Char('x'), "q"),
+            ],
+            ..Settings::default()
+        };
+        let defaults = vec![KeySettingItem::action(
+            "normal",
+            SerializableKey::Ctrl('p'),
+            AppAction::PaletteOpen,
+            "Open the command palette",
+        )];
+        let (keymap, errors) = settings.keymap(defaults);
+        assert_eq!(keymap.len(), 2);
+        assert_eq!(keymap[0].command, Some(String::from("help")));
+        assert_eq!(keymap[1].action, Some(AppAction::PaletteOpen));
+        assert_eq!(errors, vec![String::from("x: unknown mode \"nowhere\"")]);
+    }
+
+    #[test]
+    fn bindings_need_an_action_or_a_command() {
+        let mut item = command("normal", SerializableKey::Char('q'), "q");
+        assert!(item.validate().is_ok());
+        item.action = Some(AppAction::PaletteOpen);
+        assert!(item.validate().is_err());
+        item.command = None;
+        assert!(item.validate().is_ok());
+        item.action = None;
+        assert!(item.validate().is_err());
+    }
+
+    #[test]
+    fn matches_key_sequences() {
+        let item =
+            KeySettingItem::action("normal", SerializableKey::Char('g'), AppAction::TabNext, "")
+                .followed_by(&[SerializableKey::Char('t')]);
+        let g = [SerializableKey::Char('g')];
+        let gt = [SerializableKey::Char('g'), SerializableKey::Char('t')];
+        assert!(item.applies("normal", &gt));
+        assert!(!item.applies("normal", &g));
+        assert!(!item.applies("visual", &gt));
+        assert!(item.extends("normal", &g));
+        assert!(!item.extends("normal", &gt));
+
+        let mut anywhere = item.clone();
+        anywhere.mode = None;
+        assert!(anywhere.applies("visual", &gt));
+    }
+
+    #[test]
+    fn spells_out_the_leader() {
+        let mut item = KeySettingItem::command("normal", SerializableKey::Char('q'), "quit", "");
+        item.leader = true;
+        let settings = Settings {
+            keys: vec![item],
+            leader: Some(SerializableKey::Char(',')),
+            ..Settings::default()
+        };
+        let (keymap, _) = settings.keymap(Vec::new());
+        assert!(keymap[0].applies(
+            "normal",
+            &[SerializableKey::Char(','), SerializableKey::Char('q')]
+        ));
+        assert!(!keymap[0].leader);
+    }
+
+    #[test]
+    fn names_key_sequences() {
+        assert_eq!(
+            keys_name(&[SerializableKey::Char('g'), SerializableKey::Char('t')]),
+            "gt"
+        );
+        assert_eq!(
+            keys_name(&[SerializableKey::Ctrl('w'), SerializableKey::Char('j')]),
+            "Ctrl-W j"
+        );
+        assert_eq!(
+            keys_name(&[SerializableKey::Char('\\'), SerializableKey::Char(' ')]),
+            "\\ Space"
+        );
+    }
 }
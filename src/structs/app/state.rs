@@ -2,8 +2,16 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt;
-use structs::app::Command;
-use structs::ui::TopTabs;
+use std::sync::Arc;
+use structs::app::events::Key;
+use structs::app::{
+    Alert, Command, Completion, History, KeySettingItem, Palette, Quote, RiskLimits, Ticket,
+};
+use structs::ui::{OutputBuffer, Panes, Selection, TopTabs, DEFAULT_SCROLLBACK};
+use utils::app::screen_size;
+
+/// Ended commands remembered, with their output.
+const ENDED_KEPT: usize = 100;
 
 const DATA: &'static str = r#"
 {
@@ -18,20 +26,49 @@ const DATA: &'static str = r#"
         "selection": 0
     },
     "command": "",
-    "console_output_lines": [],
     "cmd_str_queue": {},
     "cmd_running": [],
-    "cmd_ended":[] 
+    "cmd_ended":[]
 }
 "#;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct AppState {
-    pub json_store: Value,
+    pub json_store: Arc<Value>,
+    /// Lines of the console tab.
+    pub console: OutputBuffer,
+    /// Lines of each command, by uuid.
+    pub cmd_output: Arc<HashMap<String, OutputBuffer>>,
+    /// Lines kept by each output, older ones are dropped.
+    pub scrollback: usize,
     pub tabs: TopTabs,
-    pub cmd_str_queue: HashMap<String, String>,
-    pub cmd_running: Vec<Command>,
-    pub cmd_ended: Vec<Command>,
+    pub cmd_str_queue: Arc<HashMap<String, String>>,
+    pub cmd_running: Arc<Vec<Command>>,
+    pub cmd_ended: Arc<Vec<Command>>,
+    pub focused_cmd: Option<String>,
+    pub quotes: Arc<HashMap<String, Quote>>,
+    /// Subscribed to, see `Settings::markets`.
+    pub markets: Arc<Vec<String>>,
+    pub alerts: Arc<Vec<Alert>>,
+    pub alert_flash: Option<(usize, String)>,
+    pub history: History,
+    pub completion: Option<Completion>,
+    /// Char index of the cursor in `json_store["command"]`.
+    pub cmd_cursor: usize,
+    pub kill_buffer: String,
+    pub palette: Option<Palette>,
+    pub keymap: Arc<Vec<KeySettingItem>>,
+    /// Start of a key sequence, shown in the status bar until complete.
+    pub pending_keys: Vec<Key>,
+    pub panes: Panes,
+    /// Set in visual mode.
+    pub selection: Option<Selection>,
+    /// Set in ticket mode.
+    pub ticket: Option<Ticket>,
+    /// Checked by every order, see `Settings::risk`.
+    pub risk: RiskLimits,
+    /// Columns and rows of the terminal.
+    pub size: (u16, u16),
 }
 
 impl AppState {
@@ -39,16 +76,107 @@ impl AppState {
         let state: Value = serde_json::from_str(DATA).expect("JSON Error!");
 
         AppState {
-            json_store: state,
+            json_store: Arc::new(state),
+            console: OutputBuffer::default(),
+            cmd_output: Arc::new(HashMap::new()),
+            scrollback: DEFAULT_SCROLLBACK,
             tabs: TopTabs {
                 titles: vec![
                     String::from("Console")
                 ],
                 selection: 0,
+                uuids: vec![None],
+                scroll: vec![0],
             },
-            cmd_str_queue: HashMap::new(),
-            cmd_running: Vec::new(),
-            cmd_ended: Vec::new(),
+            cmd_str_queue: Arc::new(HashMap::new()),
+            cmd_running: Arc::new(Vec::new()),
+            cmd_ended: Arc::new(Vec::new()),
+            focused_cmd: None,
+            quotes: Arc::new(HashMap::new()),
+            markets: Arc::new(Vec::new()),
+            alerts: Arc::new(Vec::new()),
+            alert_flash: None,
+            history: History::default(),
+            completion: None,
+            cmd_cursor: 0,
+            kill_buffer: String::new(),
+            palette: None,
+            keymap: Arc::new(Vec::new()),
+            pending_keys: Vec::new(),
+            panes: Panes::new(),
+            selection: None,
+            ticket: None,
+            risk: RiskLimits::default(),
+            size: screen_size(),
+        }
+    }
+
+    /// Output of the selected tab, the console or a command's, cheap to
+    /// clone.
+    pub fn output_lines(&self) -> OutputBuffer {
+        match self.tabs.selected_uuid() {
+            Some(uuid) => self.cmd_output.get(uuid).cloned().unwrap_or_default(),
+            None => self.console.clone(),
+        }
+    }
+
+    /// Appends to the console (`None`) or to a command's output, the
+    /// oldest lines dropped past the scrollback.
+    pub fn push_output(&mut self, uuid: Option<&str>, line: Value) {
+        let output = match uuid {
+            Some(uuid) => Arc::make_mut(&mut self.cmd_output)
+                .entry(uuid.to_string())
+                .or_insert_with(OutputBuffer::default),
+            None => &mut self.console,
+        };
+        let dropped = output.push(line, self.scrollback);
+        self.tabs.grew(uuid);
+        // the visual selection counts lines from the top
+        let selected = self.tabs.selected_uuid().map(|id| id.as_str()) == uuid;
+        if let (Some(selection), true) = (self.selection.as_mut(), selected) {
+            selection.anchor = selection.anchor.saturating_sub(dropped);
+            selection.cursor = selection.cursor.saturating_sub(dropped);
+        }
+    }
+
+    /// Forgets the oldest ended commands past `ENDED_KEPT`, and their
+    /// output, unless a tab shows them.
+    pub fn prune_ended(&mut self) {
+        let mut excess = self.cmd_ended.len().saturating_sub(ENDED_KEPT);
+        if excess == 0 {
+            return;
+        }
+        let tabs = &self.tabs;
+        let cmd_output = Arc::make_mut(&mut self.cmd_output);
+        Arc::make_mut(&mut self.cmd_ended).retain(|cmd| {
+            let shown = tabs.uuids.iter().any(|id| id.as_ref() == Some(&cmd.id));
+            if excess == 0 || shown {
+                return true;
+            }
+            excess -= 1;
+            cmd_output.remove(&cmd.id);
+            false
+        });
+    }
+
+    /// Rows of the output pane, the terminal less the tabs, status and
+    /// command bars.
+    pub fn page_height(&self) -> usize {
+        (self.size.1 as usize).saturating_sub(3).max(1)
+    }
+
+    /// Text of the lines selected in visual mode.
+    pub fn selected_text(&self) -> Option<String> {
+        let (first, last) = self.selection?.range();
+        let output = self.output_lines();
+        let lines: Vec<&str> = output
+            .range(first, last + 1)
+            .map(OutputBuffer::text)
+            .map(|line| line.trim_end_matches('\n'))
+            .collect();
+        match lines.is_empty() {
+            true => None,
+            false => Some(lines.join("\n")),
         }
     }
 }
@@ -66,3 +194,33 @@ impl fmt::Debug for AppState {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ended(state: &mut AppState, id: usize) {
+        let id = id.to_string();
+        state.push_output(Some(&id), Value::String(String::from("line")));
+        let cmd = Command::new(String::from("exec true"), id, false);
+        Arc::make_mut(&mut state.cmd_ended).push(cmd);
+        state.prune_ended();
+    }
+
+    #[test]
+    fn forgets_the_oldest_ended_commands() {
+        let mut state = AppState::new();
+        state.tabs.open(String::from("true 1"), "1");
+        for id in 0..ENDED_KEPT + 2 {
+            ended(&mut state, id);
+        }
+        let ids: Vec<&str> = state.cmd_ended.iter().map(|cmd| cmd.id.as_str()).collect();
+        assert_eq!(ids.len(), ENDED_KEPT);
+        // the oldest but the one shown in a tab
+        assert_eq!(&ids[..3], &["1", "3", "4"]);
+        assert!(!state.cmd_output.contains_key("0"));
+        assert!(!state.cmd_output.contains_key("2"));
+        assert!(state.cmd_output.contains_key("1"));
+        assert_eq!(state.cmd_output.len(), ENDED_KEPT);
+    }
+}
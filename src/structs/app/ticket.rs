@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use structs::app::{Order, RiskLimits, Side};
+
+pub const TICKET_FIELDS: [&'static str; 3] = ["symbol", "amount", "price"];
+
+/// The order filled in while in ticket mode, placed with `:order`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ticket {
+    pub side: Side,
+    /// Values of `TICKET_FIELDS`, in the same order.
+    pub values: Vec<String>,
+    /// Index of the field being typed in.
+    pub selected: usize,
+    /// Why the last submit was refused.
+    pub error: Option<String>,
+}
+
+impl Ticket {
+    /// Starts on the amount, the symbol and price being prefilled.
+    pub fn new(side: Side, symbol: &str, price: Option<f64>) -> Ticket {
+        Ticket {
+            side,
+            values: vec![
+                symbol.to_string(),
+                String::new(),
+                price
+                    .map(|price| format!("{:.8}", price))
+                    .unwrap_or_default(),
+            ],
+            selected: 1,
+            error: None,
+        }
+    }
+
+    pub fn value(&self, field: &str) -> &str {
+        TICKET_FIELDS
+            .iter()
+            .position(|name| *name == field)
+            .map_or("", |index| self.values[index].as_str())
+    }
+
+    pub fn select(&mut self, step: isize) {
+        let len = TICKET_FIELDS.len() as isize;
+        self.selected = (self.selected as isize + step).rem_euclid(len) as usize;
+    }
+
+    pub fn toggle_side(&mut self) {
+        self.side = match self.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.values[self.selected].push(c);
+        self.error = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.values[self.selected].pop();
+        self.error = None;
+    }
+
+    /// The order placed by this ticket, if within `risk`.
+    pub fn order(&self, risk: &RiskLimits) -> Result<Order, String> {
+        let number = |field: &str| {
+            self.value(field)
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid {}", field))
+        };
+        let order = Order::new(
+            self.side,
+            self.value("symbol"),
+            number("amount")?,
+            number("price")?,
+        )?;
+        risk.check(&order)?;
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(amount: &str, price: &str) -> Ticket {
+        let mut ticket = Ticket::new(Side::Buy, "BTC_ETH", None);
+        ticket.values[1] = amount.to_string();
+        ticket.values[2] = price.to_string();
+        ticket
+    }
+
+    #[test]
+    fn prefills_the_symbol_and_price() {
+        let ticket = Ticket::new(Side::Sell, "BTC_ETH", Some(0.5));
+        assert_eq!(ticket.value("symbol"), "BTC_ETH");
+        assert_eq!(ticket.value("amount"), "");
+        assert_eq!(ticket.value("price"), "0.50000000");
+        assert_eq!(ticket.value("nothing"), "");
+        assert_eq!(TICKET_FIELDS[ticket.selected], "amount");
+    }
+
+    #[test]
+    fn cycles_fields_and_sides() {
+        let mut ticket = ticket("", "");
+        ticket.select(1);
+        assert_eq!(ticket.selected, 2);
+        ticket.select(1);
+        assert_eq!(ticket.selected, 0);
+        ticket.select(-1);
+        assert_eq!(ticket.selected, 2);
+
+        ticket.toggle_side();
+        assert_eq!(ticket.side, Side::Sell);
+        ticket.toggle_side();
+        assert_eq!(ticket.side, Side::Buy);
+    }
+
+    #[test]
+    fn edits_the_selected_field() {
+        let mut ticket = ticket("", "");
+        ticket.error = Some(String::from("Invalid amount"));
+        ticket.push('1');
+        ticket.push('2');
+        ticket.pop();
+        assert_eq!(ticket.value("amount"), "1");
+        assert_eq!(ticket.error, None);
+    }
+
+    #[test]
+    fn places_orders() {
+        let none = RiskLimits::default();
+        let order = ticket(" 1.5 ", "0.03").order(&none).expect("order");
+        assert_eq!(order.command(), "order buy BTC_ETH 1.5 0.03");
+
+        assert_eq!(
+            ticket("", "0.03").order(&none),
+            Err(String::from("Invalid amount"))
+        );
+        assert_eq!(
+            ticket("1", "x").order(&none),
+            Err(String::from("Invalid price"))
+        );
+        assert_eq!(
+            ticket("0", "1").order(&none),
+            Err(String::from("Invalid amount"))
+        );
+
+        let limits = RiskLimits {
+            max_amount: Some(1.0),
+            max_notional: None,
+        };
+        assert!(ticket("2", "1").order(&limits).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CursorMove {
+    Left,
+    Right,
+    /// To the start of the current or previous word.
+    WordLeft,
+    /// To the end of the current or next word.
+    WordRight,
+    Home,
+    End,
+}
+
+impl CursorMove {
+    /// Where the cursor at char index `cursor` of `line` ends up, never
+    /// before `start` (the mode prefix of the command bar).
+    pub fn target(self, line: &str, cursor: usize, start: usize) -> usize {
+        let chars: Vec<char> = line.chars().collect();
+        let cursor = cursor.max(start).min(chars.len());
+        match self {
+            CursorMove::Left => cursor.saturating_sub(1).max(start),
+            CursorMove::Right => (cursor + 1).min(chars.len()),
+            CursorMove::Home => start,
+            CursorMove::End => chars.len(),
+            CursorMove::WordLeft => {
+                let mut target = cursor;
+                while target > start && chars[target - 1].is_whitespace() {
+                    target -= 1;
+                }
+                while target > start && !chars[target - 1].is_whitespace() {
+                    target -= 1;
+                }
+                target
+            }
+            CursorMove::WordRight => {
+                let mut target = cursor;
+                while target < chars.len() && chars[target].is_whitespace() {
+                    target += 1;
+                }
+                while target < chars.len() && !chars[target].is_whitespace() {
+                    target += 1;
+                }
+                target
+            }
+        }
+    }
+}
+
+/// Byte offset of the char at `index`, or the length past the last one.
+pub fn byte_index(line: &str, index: usize) -> usize {
+    line.char_indices()
+        .nth(index)
+        .map_or(line.len(), |(offset, _)| offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_by_chars_within_the_line() {
+        assert_eq!(CursorMove::Left.target(":abc", 2, 1), 1);
+        assert_eq!(CursorMove::Left.target(":abc", 1, 1), 1);
+        assert_eq!(CursorMove::Right.target(":abc", 3, 1), 4);
+        assert_eq!(CursorMove::Right.target(":abc", 4, 1), 4);
+        assert_eq!(CursorMove::Home.target(":abc", 3, 1), 1);
+        assert_eq!(CursorMove::End.target(":abc", 1, 1), 4);
+        // a cursor out of range is brought back first
+        assert_eq!(CursorMove::Left.target(":abc", 9, 1), 3);
+        assert_eq!(CursorMove::Right.target(":abc", 0, 1), 2);
+    }
+
+    #[test]
+    fn moves_by_words() {
+        let line = ":exec  ls -l";
+        assert_eq!(CursorMove::WordLeft.target(line, 12, 1), 10);
+        assert_eq!(CursorMove::WordLeft.target(line, 10, 1), 7);
+        assert_eq!(CursorMove::WordLeft.target(line, 7, 1), 1);
+        assert_eq!(CursorMove::WordLeft.target(line, 1, 1), 1);
+        assert_eq!(CursorMove::WordRight.target(line, 1, 1), 5);
+        assert_eq!(CursorMove::WordRight.target(line, 5, 1), 9);
+        assert_eq!(CursorMove::WordRight.target(line, 9, 1), 12);
+        assert_eq!(CursorMove::WordRight.target(line, 12, 1), 12);
+    }
+
+    #[test]
+    fn counts_chars_not_bytes() {
+        assert_eq!(CursorMove::End.target(":é", 1, 1), 2);
+        assert_eq!(byte_index(":é!", 2), 3);
+        assert_eq!(byte_index(":é", 5), 3);
+    }
+}
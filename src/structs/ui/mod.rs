@@ -1,2 +1,10 @@
+mod cursor;
+mod output;
+mod panes;
+mod selection;
 mod tabs;
+pub use self::cursor::{byte_index, CursorMove};
+pub use self::output::{OutputBuffer, DEFAULT_SCROLLBACK};
+pub use self::panes::{Pane, Panes, Scroll};
+pub use self::selection::Selection;
 pub use self::tabs::TopTabs;
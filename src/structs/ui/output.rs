@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+// Lines per chunk, a push copies at most this many when the chunk is shared.
+const CHUNK_LINES: usize = 256;
+
+/// Lines kept by each output unless `settings.json` says otherwise.
+pub const DEFAULT_SCROLLBACK: usize = 10000;
+
+/// Output lines of the console or of a command.
+///
+/// Lines are kept in shared chunks, so cloning the state, which happens on
+/// every action and every render, copies a pointer rather than the lines.
+/// Only the chunk being appended to is copied, and only while an older
+/// state still holds it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OutputBuffer {
+    chunks: Arc<VecDeque<Arc<Vec<Value>>>>,
+    /// Lines of the first chunk already dropped.
+    head: usize,
+    len: usize,
+    /// Lines dropped so far to stay within the scrollback.
+    dropped: usize,
+}
+
+impl OutputBuffer {
+    /// Appends `line`, then drops the oldest lines past `limit`. Returns
+    /// how many were dropped.
+    pub fn push(&mut self, line: Value, limit: usize) -> usize {
+        {
+            let chunks = Arc::make_mut(&mut self.chunks);
+            let full = chunks
+                .back()
+                .map_or(true, |chunk| chunk.len() >= CHUNK_LINES);
+            if full {
+                chunks.push_back(Arc::new(Vec::with_capacity(CHUNK_LINES)));
+            }
+            Arc::make_mut(chunks.back_mut().expect("a chunk")).push(line);
+        }
+        self.len += 1;
+        let excess = self.len.saturating_sub(limit.max(1));
+        for _ in 0..excess {
+            self.drop_first();
+        }
+        excess
+    }
+
+    // Chunks other than the first and the last are full, a chunk is only
+    // let go of once all its lines are dropped.
+    fn drop_first(&mut self) {
+        self.head += 1;
+        self.len -= 1;
+        self.dropped += 1;
+        if self.head >= self.chunks.front().map_or(0, |chunk| chunk.len()) {
+            Arc::make_mut(&mut self.chunks).pop_front();
+            self.head = 0;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = OutputBuffer::default();
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Lines `start` to `end`, `end` excluded, skipping the chunks before.
+    pub fn range<'a>(&'a self, start: usize, end: usize) -> impl Iterator<Item = &'a Value> + 'a {
+        let end = end.min(self.len);
+        let start = start.min(end);
+        let first = self.head + start;
+        self.chunks
+            .iter()
+            .skip(first / CHUNK_LINES)
+            .flat_map(|chunk| chunk.iter())
+            .skip(first % CHUNK_LINES)
+            .take(end - start)
+    }
+
+    /// Text of a line, plain or highlighted.
+    pub fn text(line: &Value) -> &str {
+        line.as_str()
+            .or_else(|| line["text"].as_str())
+            .unwrap_or("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn filled(lines: usize, limit: usize) -> OutputBuffer {
+        let mut output = OutputBuffer::default();
+        for line in 0..lines {
+            output.push(Value::from(line), limit);
+        }
+        output
+    }
+
+    fn numbers(output: &OutputBuffer, start: usize, end: usize) -> Vec<u64> {
+        output
+            .range(start, end)
+            .map(|line| line.as_u64().expect("a number"))
+            .collect()
+    }
+
+    #[test]
+    fn reads_ranges_across_chunks() {
+        let output = filled(CHUNK_LINES * 2 + 10, usize::max_value());
+        assert_eq!(output.len(), CHUNK_LINES * 2 + 10);
+        assert_eq!(output.chunks.len(), 3);
+        let start = CHUNK_LINES - 2;
+        assert_eq!(numbers(&output, start, start + 4), vec![254, 255, 256, 257]);
+        // clamped to the lines there are
+        let end = output.len();
+        assert_eq!(numbers(&output, end - 1, end + 5), vec![end as u64 - 1]);
+        assert!(numbers(&output, 5, 2).is_empty());
+    }
+
+    #[test]
+    fn drops_lines_past_the_limit() {
+        let mut output = filled(CHUNK_LINES + 5, CHUNK_LINES);
+        assert_eq!(output.len(), CHUNK_LINES);
+        assert_eq!(output.dropped(), 5);
+        assert_eq!(numbers(&output, 0, 2), vec![5, 6]);
+
+        // the first chunk goes once all its lines are dropped
+        for line in 0..CHUNK_LINES {
+            assert_eq!(output.push(Value::from(line), CHUNK_LINES), 1);
+        }
+        assert_eq!(output.chunks.len(), 2);
+        assert_eq!(output.dropped(), CHUNK_LINES + 5);
+        assert_eq!(numbers(&output, 0, 1), vec![0]);
+    }
+
+    #[test]
+    fn clones_share_their_lines() {
+        let mut output = filled(CHUNK_LINES + 1, usize::max_value());
+        let snapshot = output.clone();
+        output.push(Value::from("new"), usize::max_value());
+        assert_eq!(snapshot.len(), CHUNK_LINES + 1);
+        assert_eq!(output.len(), CHUNK_LINES + 2);
+        // only the chunk appended to was copied
+        assert!(Arc::ptr_eq(&snapshot.chunks[0], &output.chunks[0]));
+        assert!(!Arc::ptr_eq(&snapshot.chunks[1], &output.chunks[1]));
+    }
+
+    #[test]
+    fn clears_every_line() {
+        let mut output = filled(CHUNK_LINES + 1, usize::max_value());
+        output.clear();
+        assert!(output.is_empty());
+        assert_eq!(numbers(&output, 0, 1), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn reads_plain_and_highlighted_text() {
+        assert_eq!(OutputBuffer::text(&Value::from("plain")), "plain");
+        let highlighted = json!({ "text": "red", "fg": "red" });
+        assert_eq!(OutputBuffer::text(&highlighted), "red");
+        assert_eq!(OutputBuffer::text(&Value::from(1)), "");
+    }
+}
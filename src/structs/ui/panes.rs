@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Pane {
+    /// The console, or the output of the selected tab.
+    Output,
+    /// Quotes of the markets streamed so far, shown once there are some.
+    Markets,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Scroll {
+    LineUp,
+    LineDown,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+}
+
+impl Scroll {
+    /// Where `position` ends up in a list of `len` rows, `page` of them
+    /// visible at once, counting from the top.
+    pub fn target(self, position: usize, len: usize, page: usize) -> usize {
+        let last = len.saturating_sub(1);
+        let target = match self {
+            Scroll::LineUp => position.saturating_sub(1),
+            Scroll::LineDown => position + 1,
+            Scroll::PageUp => position.saturating_sub(page.max(1)),
+            Scroll::PageDown => position + page.max(1),
+            Scroll::Top => 0,
+            Scroll::Bottom => last,
+        };
+        target.min(last)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Panes {
+    pub focused: Pane,
+    /// Selected row of the markets pane.
+    pub market: usize,
+}
+
+impl Panes {
+    pub fn new() -> Panes {
+        Panes {
+            focused: Pane::Output,
+            market: 0,
+        }
+    }
+
+    /// Focuses the other pane, unless it isn't shown.
+    pub fn next(&mut self, markets_shown: bool) {
+        self.focused = match self.focused {
+            Pane::Output if markets_shown => Pane::Markets,
+            _ => Pane::Output,
+        };
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Lines selected in visual mode, indexes into the output of the selected
+/// tab.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Selection {
+    /// Where the selection started.
+    pub anchor: usize,
+    /// The end that moves.
+    pub cursor: usize,
+}
+
+impl Selection {
+    pub fn new(line: usize) -> Selection {
+        Selection {
+            anchor: line,
+            cursor: line,
+        }
+    }
+
+    /// First and last selected lines.
+    pub fn range(&self) -> (usize, usize) {
+        (self.anchor.min(self.cursor), self.anchor.max(self.cursor))
+    }
+
+    pub fn contains(&self, line: usize) -> bool {
+        let (first, last) = self.range();
+        first <= line && line <= last
+    }
+}
@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 pub struct TopTabs {
     pub titles: Vec<String>,
     pub selection: usize,
+    /// Command shown by each tab, `None` for the shared console.
+    pub uuids: Vec<Option<String>>,
+    /// Lines each tab is scrolled back from the end of its output, 0 while
+    /// following it.
+    pub scroll: Vec<usize>,
 }
 
 impl TopTabs {
@@ -18,4 +23,63 @@ impl TopTabs {
             self.selection = self.titles.len() - 1;
         }
     }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.titles.len() {
+            self.selection = index;
+        }
+    }
+
+    /// Selects the tab of `uuid`, opening it first if needed.
+    pub fn open(&mut self, title: String, uuid: &str) {
+        let existing = self
+            .uuids
+            .iter()
+            .position(|id| id.as_ref().map(|id| id.as_str()) == Some(uuid));
+        self.selection = match existing {
+            Some(index) => index,
+            None => {
+                self.titles.push(title);
+                self.uuids.push(Some(uuid.to_string()));
+                self.scroll.push(0);
+                self.titles.len() - 1
+            }
+        };
+    }
+
+    /// Closes a command tab, the console tab can't be closed.
+    pub fn close(&mut self, index: usize) -> bool {
+        if index == 0 || index >= self.titles.len() {
+            return false;
+        }
+        self.titles.remove(index);
+        self.uuids.remove(index);
+        self.scroll.remove(index);
+        if self.selection >= index {
+            self.selection -= 1;
+        }
+        true
+    }
+
+    pub fn selected_uuid(&self) -> Option<&String> {
+        self.uuids.get(self.selection).and_then(|id| id.as_ref())
+    }
+
+    pub fn selected_scroll(&self) -> usize {
+        self.scroll.get(self.selection).cloned().unwrap_or(0)
+    }
+
+    /// A line was added to the output of `uuid` (the console for `None`),
+    /// tabs scrolled back keep showing the same lines.
+    pub fn grew(&mut self, uuid: Option<&str>) {
+        let index = self
+            .uuids
+            .iter()
+            .position(|id| id.as_ref().map(|id| id.as_str()) == uuid);
+        if let Some(index) = index {
+            if self.scroll[index] > 0 {
+                self.scroll[index] += 1;
+            }
+        }
+    }
 }
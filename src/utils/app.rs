@@ -35,8 +35,9 @@
 //         }
 //     }
 // }
-use structs::app::events::Key;
+use structs::app::events::{Key, Mouse, MouseButton};
 use termion::event;
+use termion::terminal_size;
 
 pub fn to_serializable(event: event::Key) -> Key {
     match event {
@@ -85,3 +86,23 @@ pub fn to_unserializable(event: Key) -> event::Key {
         Key::__IsNotComplete => event::Key::__IsNotComplete,
     }
 }
+
+pub fn to_serializable_mouse(event: event::MouseEvent) -> Mouse {
+    let button = |button: event::MouseButton| match button {
+        event::MouseButton::Left => MouseButton::Left,
+        event::MouseButton::Right => MouseButton::Right,
+        event::MouseButton::Middle => MouseButton::Middle,
+        event::MouseButton::WheelUp => MouseButton::WheelUp,
+        event::MouseButton::WheelDown => MouseButton::WheelDown,
+    };
+    match event {
+        event::MouseEvent::Press(_button, x, y) => Mouse::Press(button(_button), x, y),
+        event::MouseEvent::Release(x, y) => Mouse::Release(x, y),
+        event::MouseEvent::Hold(x, y) => Mouse::Hold(x, y),
+    }
+}
+
+/// Columns and rows of the terminal.
+pub fn screen_size() -> (u16, u16) {
+    terminal_size().unwrap_or((80, 24))
+}
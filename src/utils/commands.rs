@@ -1,3 +1,4 @@
+use actions::AppAction;
 use redux::Store;
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
@@ -5,15 +6,23 @@ use std::thread;
 use structs::app::events::Event;
 use structs::app::AppState;
 
-pub fn connect(receiver: Receiver<Event>, store: Arc<Store<AppState>>, exit_tx: Sender<Event>) {
+pub fn connect(receiver: Receiver<Event>, store: Arc<Store<AppState>>, render_tx: Sender<Event>) {
     thread::spawn(move || loop {
         match receiver.recv().unwrap() {
             Event::Dispatch(action) => {
                 debug!("ACTION DISPATCHED {:?}", &action);
                 let _ = store.dispatch(action);
             }
+            Event::Clipboard(text) => {
+                let _ = render_tx.send(Event::Clipboard(text));
+            }
+            Event::Resize(cols, rows) => {
+                // the last frame is redrawn right away, the store catches up
+                let _ = render_tx.send(Event::Resize(cols, rows));
+                let _ = store.dispatch(AppAction::Resize(cols, rows));
+            }
             Event::Exit => {
-                let _ = exit_tx.send(Event::Exit);
+                let _ = render_tx.send(Event::Exit);
                 break;
             }
             _ => {}
@@ -1,5 +1,9 @@
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::PathBuf;
+
+const CONFIG_DIR: &'static str = ".cryptocmd";
 
 pub fn read_file(path: &str) -> String {
     let mut file = File::open(path).unwrap();
@@ -7,3 +11,9 @@ pub fn read_file(path: &str) -> String {
     let _res = file.read_to_string(&mut contents);
     contents
 }
+
+/// Resolves `name` inside the config directory (`~/.cryptocmd`).
+pub fn config_path(name: &str) -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| String::from("."));
+    PathBuf::from(home).join(CONFIG_DIR).join(name)
+}
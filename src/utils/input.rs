@@ -1,16 +1,41 @@
 use actions::AppAction;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 use std::{io, thread};
 use structs::app::events::Event;
+use termion::event::Event as TermEvent;
 use termion::input::TermRead;
-use utils::app::to_serializable;
+use utils::app::{screen_size, to_serializable, to_serializable_mouse};
+
+// How often the terminal size is checked.
+const RESIZE_POLL: Duration = Duration::from_millis(200);
 
 pub fn init(input_tx: Sender<Event>) {
     thread::spawn(move || {
-        for c in io::stdin().keys() {
-            let serializable = to_serializable(c.unwrap());
-            let evt = AppAction::Keyboard(serializable).into_event();
-            input_tx.send(evt).expect("Failed to Send");
+        for evt in io::stdin().events() {
+            let action = match evt.unwrap() {
+                TermEvent::Key(key) => AppAction::Keyboard(to_serializable(key)),
+                TermEvent::Mouse(mouse) => AppAction::Mouse(to_serializable_mouse(mouse)),
+                TermEvent::Unsupported(_) => continue,
+            };
+            input_tx.send(action.into_event()).expect("Failed to Send");
+        }
+    });
+}
+
+// termion has no resize event, polling saves a SIGWINCH handler.
+pub fn watch_size(resize_tx: Sender<Event>) {
+    thread::spawn(move || {
+        let mut size = screen_size();
+        loop {
+            thread::sleep(RESIZE_POLL);
+            let current = screen_size();
+            if current != size {
+                size = current;
+                if resize_tx.send(Event::Resize(size.0, size.1)).is_err() {
+                    break;
+                }
+            }
         }
     });
 }
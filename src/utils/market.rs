@@ -0,0 +1,106 @@
+use actions::AppAction;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use structs::app::events::Event;
+use structs::app::{Orderbook, Quote};
+use ws::{Handler, Handshake, Message, Sender as WsSender};
+
+/// Feed used unless `settings.json` names another.
+pub const DEFAULT_FEED: &'static str = "wss://api2.poloniex.com";
+const HEARTBEAT_CHANNEL: u64 = 1010;
+const RECONNECT_DELAY: u64 = 5;
+
+/// Markets subscribed to unless `settings.json` lists others.
+pub const DEFAULT_MARKETS: [&'static str; 1] = ["BTC_ETH"];
+
+struct Client {
+    out: WsSender,
+    tx: Sender<Event>,
+    markets: Vec<String>,
+    books: HashMap<u64, Orderbook>,
+}
+
+impl Client {
+    // [channel, sequence, [["i", snapshot] | ["o", is_bid, price, size] | ["t", ...]]]
+    fn parse(&mut self, raw: &Value) -> Option<Quote> {
+        let channel = raw[0].as_u64()?;
+        if channel == HEARTBEAT_CHANNEL {
+            return None;
+        }
+        for update in raw[2].as_array()? {
+            match update[0].as_str() {
+                Some("i") => {
+                    self.books
+                        .insert(channel, Orderbook::from_snapshot(&update[1]));
+                }
+                Some("o") => {
+                    if let Some(book) = self.books.get_mut(&channel) {
+                        let price = update[2].as_str().unwrap_or("0").parse::<f64>();
+                        let size = update[3].as_str().unwrap_or("0").parse::<f64>();
+                        if let (Ok(price), Ok(size)) = (price, size) {
+                            book.apply(update[1].as_u64() == Some(1), price, size);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.books
+            .get(&channel)
+            .map(|book| Quote::from_book(book, now_millis()))
+    }
+}
+
+impl Handler for Client {
+    fn on_open(&mut self, _: Handshake) -> ws::Result<()> {
+        for market in &self.markets {
+            let sub = json!({"command": "subscribe", "channel": market});
+            self.out.send(sub.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn on_message(&mut self, msg: Message) -> ws::Result<()> {
+        let raw: Value = match msg.as_text().map(serde_json::from_str) {
+            Ok(Ok(value)) => value,
+            _ => return Ok(()),
+        };
+        if let Some(quote) = self.parse(&raw) {
+            let _ = self.tx.send(AppAction::QuoteUpdate(quote).into_event());
+        }
+        Ok(())
+    }
+}
+
+/// Subscribes to the order books of `markets` on `feed` and dispatches a
+/// `QuoteUpdate` for every book change, reconnecting whenever the socket
+/// drops. Stays offline when there are no markets.
+pub fn connect(tx: Sender<Event>, feed: &str, markets: &[String]) {
+    if markets.is_empty() {
+        return;
+    }
+    let feed = feed.to_string();
+    let markets = markets.to_vec();
+    thread::spawn(move || loop {
+        let res = ws::connect(feed.as_str(), |out| Client {
+            out,
+            tx: tx.clone(),
+            markets: markets.clone(),
+            books: HashMap::new(),
+        });
+        if let Err(err) = res {
+            error!("Market Feed Error: {:?}", err);
+        }
+        thread::sleep(Duration::from_secs(RECONNECT_DELAY));
+    });
+}
+
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
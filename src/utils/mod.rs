@@ -2,8 +2,12 @@
 #[macro_use]
 pub mod format;
 pub mod app;
+pub mod clipboard;
 pub mod commands;
 pub mod fs;
 pub mod input;
+pub mod market;
+pub mod python;
 pub mod run;
+pub mod shell;
 pub mod store;
@@ -1,8 +1,11 @@
-use cpython::{PyDict, PyResult, Python, ToPyObject};
+use cpython::{FromPyObject, PyDict, PyResult, PyTuple, Python, ToPyObject};
 
-pub fn run<TPO>(py: Python, data: &Vec<TPO>, code: &String) -> PyResult<i64>
+/// Runs `code`, exposes `data` as a global and returns whatever `main()`
+/// yields, e.g. an `i64` signal or a list of `Quote`s.
+pub fn run<TPO, R>(py: Python, data: &Vec<TPO>, code: &String) -> PyResult<R>
 where
     TPO: ToPyObject,
+    R: for<'a> FromPyObject<'a>,
 {
     match py.run(code, None, None) {
         Ok(_) => {
@@ -16,3 +19,24 @@ where
         }
     }
 }
+
+/// Runs `code` once in a module of its own, returning its globals.
+pub fn load(py: Python, name: &str, code: &str) -> PyResult<PyDict> {
+    let globals = PyDict::new(py);
+    globals.set_item(py, "__name__", name)?;
+    py.run(code, Some(&globals), None)?;
+    Ok(globals)
+}
+
+/// Calls function `name` of a module loaded by `load`, `None` when the
+/// module doesn't define it.
+pub fn call<A, R>(py: Python, globals: &PyDict, name: &str, args: A) -> PyResult<Option<R>>
+where
+    A: ToPyObject<ObjectType = PyTuple>,
+    R: for<'a> FromPyObject<'a>,
+{
+    match globals.get_item(py, name) {
+        Some(function) => Ok(Some(function.call(py, args, None)?.extract(py)?)),
+        None => Ok(None),
+    }
+}
@@ -1,7 +1,10 @@
 use components::app;
 use std::io;
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
 use structs::app::events::Event;
+use structs::app::AppState;
+use utils::clipboard;
 
 use termion::input::MouseTerminal;
 use termion::raw::IntoRawMode;
@@ -10,7 +13,7 @@ use termion::screen::AlternateScreen;
 use tui::backend::TermionBackend;
 use tui::Terminal;
 
-pub fn keep_alive(receiver: Receiver<Event>) -> Result<(), io::Error> {
+pub fn keep_alive(receiver: Receiver<Event>, frame_interval: Duration) -> Result<(), io::Error> {
     // Terminal initialization
     let stdout = io::stdout().into_raw_mode()?;
     let stdout = MouseTerminal::from(stdout);
@@ -20,14 +23,46 @@ pub fn keep_alive(receiver: Receiver<Event>) -> Result<(), io::Error> {
     terminal.clear().unwrap();
     terminal.hide_cursor()?;
 
+    // states rendered since the last frame, only the latest gets drawn
+    let mut pending: Option<AppState> = None;
+    // kept to redraw on resize, without waiting for the next action
+    let mut last: Option<AppState> = None;
+    let mut drawn_at: Option<Instant> = None;
     loop {
-        let _ = match receiver.recv().unwrap() {
-            Event::Render(app_state) => terminal.draw(|mut f| app::render(&mut f, &app_state)),
+        let wait = drawn_at.map_or(Duration::default(), |at| {
+            frame_interval.checked_sub(at.elapsed()).unwrap_or_default()
+        });
+        if pending.is_some() && wait == Duration::default() {
+            let app_state = pending.take().expect("pending state");
+            let _ = terminal.draw(|mut f| app::render(&mut f, &app_state));
+            drawn_at = Some(Instant::now());
+            last = Some(app_state);
+            continue;
+        }
+        // with a frame pending, wait no longer than until it's due
+        let event = match pending {
+            Some(_) => match receiver.recv_timeout(wait) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match receiver.recv() {
+                Ok(event) => event,
+                Err(_) => break,
+            },
+        };
+        match event {
+            Event::Render(app_state) => pending = Some(app_state),
+            // drawing resizes the terminal's buffers, and clears the screen
+            Event::Resize(..) if pending.is_none() => pending = last.clone(),
+            Event::Clipboard(text) => {
+                let _ = clipboard::copy(terminal.backend_mut(), &text);
+            }
             Event::Exit => {
                 break;
             }
-            _ => Ok(()),
-        };
+            _ => {}
+        }
     }
 
     // show cursor on end
@@ -0,0 +1,316 @@
+use std::env;
+use std::iter::Peekable;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Word(String),
+    /// `|`
+    Pipe,
+    /// `>`
+    Write,
+    /// `>>`
+    Append,
+    /// `<`
+    Read,
+}
+
+impl Token {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Token::Word(word) => word.as_str(),
+            Token::Pipe => "|",
+            Token::Write => ">",
+            Token::Append => ">>",
+            Token::Read => "<",
+        }
+    }
+}
+
+/// Splits a command line into words the way a POSIX shell would: words are
+/// separated by any amount of whitespace, `'...'` is taken literally, `"..."`
+/// expands variables and honours `\"`, `\\` and `\$`, a backslash outside
+/// quotes escapes the next character, `$VAR` / `${VAR}` are expanded from the
+/// environment and a leading `~` becomes `$HOME`. Unquoted `|`, `>`, `>>` and
+/// `<` are returned as operators rather than words, except for `>=` and `<=`
+/// which are kept as words for comparisons.
+pub fn tokenize(line: &str) -> Result<Vec<Token>, String> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut word = String::new();
+    // distinguishes an empty quoted word ("") from no word at all
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\n' | '|' | '>' | '<' => {
+                if in_word {
+                    tokens.push(Token::Word(word.clone()));
+                    word.clear();
+                    in_word = false;
+                }
+                match c {
+                    '|' => tokens.push(Token::Pipe),
+                    // comparisons, as in `:alert BTC_ETH >= 0.032`
+                    '>' | '<' if chars.peek() == Some(&'=') => {
+                        chars.next();
+                        tokens.push(Token::Word(format!("{}=", c)));
+                    }
+                    '<' => tokens.push(Token::Read),
+                    '>' if chars.peek() == Some(&'>') => {
+                        chars.next();
+                        tokens.push(Token::Append);
+                    }
+                    '>' => tokens.push(Token::Write),
+                    _ => {}
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(String::from("Unterminated single quote")),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) if c == '"' || c == '\\' || c == '$' => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(String::from("Unterminated double quote")),
+                        },
+                        Some('$') => word.push_str(&expand_var(&mut chars)?),
+                        Some(c) => word.push(c),
+                        None => return Err(String::from("Unterminated double quote")),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+                None => return Err(String::from("Trailing backslash")),
+            },
+            '$' => {
+                in_word = true;
+                word.push_str(&expand_var(&mut chars)?);
+            }
+            '~' if !in_word
+                && chars
+                    .peek()
+                    .map_or(true, |c| *c == '/' || c.is_whitespace()) =>
+            {
+                in_word = true;
+                word.push_str(&env::var("HOME").unwrap_or_else(|_| String::from("~")));
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+// Called right after a `$`, a lone `$` is kept as is.
+fn expand_var<I>(chars: &mut Peekable<I>) -> Result<String, String>
+where
+    I: Iterator<Item = char>,
+{
+    let mut name = String::new();
+    if chars.peek() == Some(&'{') {
+        chars.next();
+        loop {
+            match chars.next() {
+                Some('}') => break,
+                Some(c) => name.push(c),
+                None => return Err(String::from("Unterminated ${")),
+            }
+        }
+    } else {
+        while let Some(&c) = chars.peek() {
+            if c.is_alphanumeric() || c == '_' {
+                name.push(c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() {
+            return Ok(String::from("$"));
+        }
+    }
+    Ok(env::var(&name).unwrap_or_default())
+}
+
+/// What follows the first `count` words of `line`, unexpanded, for commands
+/// that run the rest of their line later. Words end at unquoted whitespace.
+pub fn rest(line: &str, count: usize) -> &str {
+    let mut words = 0;
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words += 1;
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                if words == count {
+                    return &line[index..];
+                }
+                in_word = true;
+                match c {
+                    '\'' | '"' => quote = Some(c),
+                    '\\' => {
+                        chars.next();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    ""
+}
+
+/// Inverse of `tokenize` for already expanded words, quoting where needed.
+pub fn join(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|token| match token {
+            Token::Word(word) => quote(word),
+            op => op.as_str().to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn quote(token: &str) -> String {
+    let plain = !token.is_empty()
+        && token
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_./:=,%@+".contains(c));
+    match plain {
+        true => token.to_string(),
+        false => format!("'{}'", token.replace('\'', "'\\''")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<Token> {
+        tokenize(line).expect("tokenized")
+    }
+
+    fn word(word: &str) -> Token {
+        Token::Word(word.to_string())
+    }
+
+    #[test]
+    fn splits_on_whitespace_and_quotes() {
+        assert_eq!(
+            words("exec  echo 'a  b' \"c d\" e\\ f"),
+            vec![
+                word("exec"),
+                word("echo"),
+                word("a  b"),
+                word("c d"),
+                word("e f")
+            ]
+        );
+        assert_eq!(words("echo ''"), vec![word("echo"), word("")]);
+    }
+
+    #[test]
+    fn reads_operators() {
+        assert_eq!(
+            words("cat < in|sort >> out > last"),
+            vec![
+                word("cat"),
+                Token::Read,
+                word("in"),
+                Token::Pipe,
+                word("sort"),
+                Token::Append,
+                word("out"),
+                Token::Write,
+                word("last"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_comparisons_as_words() {
+        assert_eq!(
+            words("alert BTC_ETH >= 0.032"),
+            vec![word("alert"), word("BTC_ETH"), word(">="), word("0.032")]
+        );
+        assert_eq!(words("a<=1"), vec![word("a"), word("<="), word("1")]);
+        assert_eq!(words("a > 1")[1], Token::Write);
+    }
+
+    #[test]
+    fn expands_variables() {
+        env::set_var("SHELL_TEST_VAR", "value");
+        assert_eq!(
+            words("$SHELL_TEST_VAR ${SHELL_TEST_VAR}s '$SHELL_TEST_VAR' \\$ $"),
+            vec![
+                word("value"),
+                word("values"),
+                word("$SHELL_TEST_VAR"),
+                word("$"),
+                word("$"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unterminated_quotes() {
+        assert!(tokenize("echo 'a").is_err());
+        assert!(tokenize("echo \"a").is_err());
+        assert!(tokenize("echo a\\").is_err());
+    }
+
+    #[test]
+    fn rest_keeps_the_line_unexpanded() {
+        assert_eq!(rest("every 1m exec echo $DATE", 2), "exec echo $DATE");
+        assert_eq!(rest("  at  '09:30'\t ~/run.sh 'a b'", 2), "~/run.sh 'a b'");
+        assert_eq!(rest("at \"9 30\" a\\ b c", 3), "c");
+        assert_eq!(rest("every 1m", 2), "");
+    }
+
+    #[test]
+    fn join_quotes_what_needs_it() {
+        let tokens = vec![
+            word("echo"),
+            word("a b"),
+            word("it's"),
+            Token::Pipe,
+            word("wc"),
+        ];
+        assert_eq!(join(&tokens), "echo 'a b' 'it'\\''s' | wc");
+        assert_eq!(words(&join(&tokens)), tokens);
+    }
+}
@@ -1,22 +1,52 @@
+use actions::AppAction;
+use commands::CommandRegistry;
 use redux::Store;
+use std::fs::{self, File, OpenOptions};
+use std::sync::atomic::AtomicUsize;
 use std::sync::mpsc::Sender;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use strategies::StrategyRegistry;
 use structs::app::events::Event;
-use structs::app::{AppState, CommandHandler};
+use structs::app::{Aliases, AppState, CommandHandler, History, Scheduler, Settings};
+use utils::fs::config_path;
 
+use middlewares::keyboard::default_keymap;
 use middlewares::{
-    CommandBarMiddleWare, CommandMiddleWare, ConsoleMiddleWare, DebugMiddleWare, KeyboardMiddleWare,
+    AlertMiddleWare, CommandBarMiddleWare, CommandMiddleWare, ConsoleMiddleWare, DebugMiddleWare,
+    HistoryMiddleWare, KeyboardMiddleWare, MouseMiddleWare, StrategyMiddleWare,
 };
 
-pub fn init(cmd_tx: &Sender<Event>) -> Arc<Store<AppState>> {
-    let keyboard_mw = Box::new(KeyboardMiddleWare {});
+pub fn init(cmd_tx: &Sender<Event>, settings: &Settings) -> Arc<Store<AppState>> {
+    let keyboard_mw = Box::new(KeyboardMiddleWare {
+        tx: cmd_tx.clone(),
+        timeout: settings.key_timeout(),
+        generation: AtomicUsize::new(0),
+    });
+    let mouse_mw = Box::new(MouseMiddleWare {});
     let command_bar_mw = Box::new(CommandBarMiddleWare {});
     let command_mw = Box::new(CommandMiddleWare {
         tx: cmd_tx.clone(),
         handler: CommandHandler::default(),
+        scheduler: Scheduler::new(cmd_tx.clone()),
+        aliases: Mutex::new(init_aliases(cmd_tx)),
+        registry: CommandRegistry::default(),
+    });
+    let console_mw = Box::new(ConsoleMiddleWare {
+        tx: cmd_tx.clone(),
+        spill: init_spill(cmd_tx, settings),
     });
-    let console_mw = Box::new(ConsoleMiddleWare {});
     let debug_mw = Box::new(DebugMiddleWare {});
+    let strategies = init_strategies(cmd_tx);
+    let strategy_mw = Box::new(StrategyMiddleWare::new(
+        strategies,
+        settings.risk.clone(),
+        cmd_tx.clone(),
+    ));
+    let alert_mw = Box::new(AlertMiddleWare { tx: cmd_tx.clone() });
+    let history_mw = Box::new(HistoryMiddleWare {
+        path: config_path("history"),
+    });
+    init_history(cmd_tx);
 
     // let (exit_tx, _exit_rx) = mpsc::channel();
     // let exit_mw = Box::new(CommandMiddleWare {
@@ -25,11 +55,85 @@ pub fn init(cmd_tx: &Sender<Event>) -> Arc<Store<AppState>> {
     // });
 
     Arc::new(Store::new(vec![
+        history_mw,
+        alert_mw,
+        strategy_mw,
         console_mw,
         command_bar_mw,
         command_mw,
         keyboard_mw,
+        mouse_mw,
         debug_mw,
         // exit_mw,
     ]))
 }
+
+// Native strategies compiled into the binary are registered here, before
+// the scripts and libraries dropped into `~/.cryptocmd/strategies`.
+fn init_strategies(cmd_tx: &Sender<Event>) -> StrategyRegistry {
+    let mut registry = StrategyRegistry::new();
+    for err in registry.load_dir(&config_path("strategies")) {
+        let line = format_output!("red", "Strategy", err);
+        let _ = cmd_tx.send(AppAction::ConsolePush(line).into_event());
+    }
+    registry
+}
+
+// Previous sessions' history arrives as an action, like any other event.
+fn init_history(cmd_tx: &Sender<Event>) {
+    match History::load(&config_path("history")) {
+        Ok(entries) => {
+            let _ = cmd_tx.send(AppAction::HistoryLoad(entries).into_event());
+        }
+        Err(err) => debug!("No history loaded {:?}", err),
+    }
+}
+
+// User bindings from `settings.json` go ahead of the defaults, the broken
+// ones are reported and skipped.
+pub fn init_settings(cmd_tx: &Sender<Event>) -> Settings {
+    let settings = Settings::load(&config_path("settings.json")).unwrap_or_else(|err| {
+        let line = format_output!("red", "Settings", err);
+        let _ = cmd_tx.send(AppAction::ConsolePush(line).into_event());
+        Settings::default()
+    });
+    let (keymap, errors) = settings.keymap(default_keymap());
+    for err in errors {
+        let line = format_output!("red", "Settings", err);
+        let _ = cmd_tx.send(AppAction::ConsolePush(line).into_event());
+    }
+    let _ = cmd_tx.send(AppAction::KeymapSet(keymap).into_event());
+    let _ = cmd_tx.send(AppAction::ScrollbackSet(settings.scrollback()).into_event());
+    let _ = cmd_tx.send(AppAction::MarketsSet(settings.markets()).into_event());
+    let _ = cmd_tx.send(AppAction::RiskSet(settings.risk.clone()).into_event());
+    settings
+}
+
+fn init_spill(cmd_tx: &Sender<Event>, settings: &Settings) -> Option<Mutex<File>> {
+    let path = config_path(settings.spill.as_ref()?);
+    let opened = match path.parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    };
+    match opened.and_then(|_| OpenOptions::new().create(true).append(true).open(&path)) {
+        Ok(file) => Some(Mutex::new(file)),
+        Err(err) => {
+            let line = format_output!("red", "Settings", format!("{}: {}", path.display(), err));
+            let _ = cmd_tx.send(AppAction::ConsolePush(line).into_event());
+            None
+        }
+    }
+}
+
+fn init_aliases(cmd_tx: &Sender<Event>) -> Aliases {
+    let path = config_path("aliases.json");
+    Aliases::load(&path).unwrap_or_else(|err| {
+        let line = format_output!("red", "Aliases", err);
+        let _ = cmd_tx.send(AppAction::ConsolePush(line).into_event());
+        // don't overwrite the broken file with nothing
+        Aliases {
+            path: path.with_file_name("aliases.json.new"),
+            ..Aliases::default()
+        }
+    })
+}